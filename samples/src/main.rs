@@ -4,7 +4,7 @@ use ssa:: {
     core::{ Dictionary },
     oracle::{
         Oracle,
-        OracleAction,
+        Registry,
     },
     dfg::{
         Network,
@@ -22,11 +22,9 @@ fn main() -> Result<()> {
     let home_dir = env!("CARGO_MANIFEST_DIR");
     let assets_dir = Path::new(home_dir).join("assets/"); 
     let contract_dir = assets_dir.join("contracts/");
-    let option = SolidityOption {
-        bin_dir: &assets_dir.join("bin/"),
-        contract: &contract_dir.join("Sample.sol"),
-        kind: SolidityOutputKind::AST,
-    };
+    let bin_dir = assets_dir.join("bin/");
+    let contract = contract_dir.join("Sample.sol");
+    let option = SolidityOption::new(&bin_dir, &contract, SolidityOutputKind::AST);
     let solidity = Solidity::new(option);
     let solidity_output = solidity.compile()?;
     // ContractDefinitionNode of HumanStandardToken
@@ -34,17 +32,18 @@ fn main() -> Result<()> {
     match solidity_output {
         SolidityOutput::AST(SolidityASTOutput { ast, sources }) => {
             let ast_json = json::parse(&ast).expect("Invalid json format");
-            let dict = Dictionary::new(&ast_json, &sources);
-            let network = Network::new(&dict, entry);
+            let dict = Dictionary::new(&ast_json, &sources).map_err(Error::other)?;
+            let network = Network::new(&dict, entry).map_err(Error::other)?;
             let mut oracle = Oracle::new(network);
+            let registry = Registry::with_builtins();
             println!("{}", oracle.format());
-            println!("==> Overflow Check");
-            for (walker, reason) in oracle.run(OracleAction::IntegerOverflow) {
+            for finding in oracle.run(&registry) {
                 println!("----");
-                println!("exp\t: {}", walker.node.source);
-                println!("reason\t: {}", reason);
+                println!("exp\t: {}", finding.get_node().get_source());
+                println!("reason\t: {}", finding.get_message());
             }
-        }
+        },
+        SolidityOutput::StandardJson(_) => unreachable!(),
     }
     Ok(())
 }
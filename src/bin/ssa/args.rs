@@ -0,0 +1,120 @@
+use std::env;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Solidity static analysis
+
+USAGE:
+    ssa [OPTIONS] <PATH>...
+
+ARGS:
+    <PATH>...    Solidity files, directories of Solidity files or compiler outputs (.json)
+
+OPTIONS:
+    -c, --contract <NAME>          Analyze only contracts named NAME, can be repeated
+    -o, --oracle <ID>              Run only the oracle ID, can be repeated
+    -f, --format <FORMAT>          Output format: text, json or dot [default: text]
+        --bin-dir <DIR>            Directory of compilers [default: ~/.ssa/bin]
+        --remap <PREFIX=TARGET>    Import remapping, can be repeated
+        --base-path <DIR>          Root of non-relative imports
+        --include-path <DIR>       Other root of non-relative imports, can be repeated
+    -h, --help                     Print help
+    -V, --version                  Print version
+
+EXIT STATUS:
+    0    No finding
+    1    At least one finding
+    2    Invalid arguments or a file can not be analyzed";
+
+/// Output format of the command line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Dot,
+}
+
+/// Parsed command line arguments
+#[derive(Debug)]
+pub struct Args {
+    pub paths: Vec<PathBuf>,
+    pub contracts: Vec<String>,
+    pub oracles: Vec<String>,
+    pub format: Format,
+    pub bin_dir: PathBuf,
+    pub remappings: Vec<String>,
+    pub base_path: Option<PathBuf>,
+    pub include_paths: Vec<PathBuf>,
+    pub help: bool,
+    pub version: bool,
+}
+
+impl Args {
+    /// Parse arguments without the program name, options accept both `--name value` and
+    /// `--name=value`, arguments after `--` are paths
+    pub fn parse<I>(args: I) -> Result<Self, String> where I: IntoIterator<Item = String> {
+        let bin_dir = env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".ssa/bin"))
+            .unwrap_or_else(|| PathBuf::from(".ssa/bin"));
+        let mut parsed = Args {
+            paths: vec![],
+            contracts: vec![],
+            oracles: vec![],
+            format: Format::Text,
+            bin_dir,
+            remappings: vec![],
+            base_path: None,
+            include_paths: vec![],
+            help: false,
+            version: false,
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                parsed.paths.extend(args.by_ref().map(PathBuf::from));
+                break;
+            }
+            if !arg.starts_with('-') || arg == "-" {
+                parsed.paths.push(PathBuf::from(arg));
+                continue;
+            }
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+                _ => (arg, None),
+            };
+            let mut value = || match inline_value.clone().or_else(|| args.next()) {
+                Some(value) => Ok(value),
+                None => Err(format!("{} requires a value", name)),
+            };
+            match name.as_str() {
+                "-c" | "--contract" => parsed.contracts.push(value()?),
+                "-o" | "--oracle" => parsed.oracles.push(value()?),
+                "-f" | "--format" => {
+                    parsed.format = match value()?.as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        "dot" => Format::Dot,
+                        format => return Err(format!("unknown format {}", format)),
+                    };
+                },
+                "--bin-dir" => parsed.bin_dir = PathBuf::from(value()?),
+                "--remap" => {
+                    let remapping = value()?;
+                    if !remapping.contains('=') {
+                        return Err(format!("invalid remapping {}", remapping));
+                    }
+                    parsed.remappings.push(remapping);
+                },
+                "--base-path" => parsed.base_path = Some(PathBuf::from(value()?)),
+                "--include-path" => parsed.include_paths.push(PathBuf::from(value()?)),
+                "-h" | "--help" => parsed.help = true,
+                "-V" | "--version" => parsed.version = true,
+                _ => return Err(format!("unknown option {}", name)),
+            }
+        }
+        if parsed.paths.is_empty() && !parsed.help && !parsed.version {
+            return Err(String::from("no input path"));
+        }
+        Ok(parsed)
+    }
+}
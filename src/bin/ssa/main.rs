@@ -0,0 +1,169 @@
+//! Command line interface of ssa
+//!
+mod args;
+mod report;
+
+use std::fs;
+use std::env;
+use std::process;
+use std::collections::HashSet;
+use std::path::{ Path, PathBuf };
+use ssa::{
+    core::{ Dictionary, Error },
+    dfg::Network,
+    oracle::{ Oracle, Registry },
+    loader::{
+        Artifact,
+        Solidity,
+        SolidityError,
+        SolidityOption,
+        SolidityOutput,
+        SolidityOutputKind,
+        SolidityASTOutput,
+    },
+};
+use args::{ Args, Format, USAGE };
+use report::Report;
+
+const EXIT_OK: i32 = 0;
+const EXIT_FINDINGS: i32 = 1;
+const EXIT_ERROR: i32 = 2;
+
+/// Find Solidity files in directories, hidden directories and `node_modules` are skipped
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<PathBuf>>>()?;
+    entries.sort();
+    for entry in entries {
+        let name = entry.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        if name.starts_with('.') || name == "node_modules" {
+            continue;
+        }
+        match entry.is_dir() {
+            true => collect_files(&entry, files)?,
+            false => if entry.extension().map(|ext| ext == "sol").unwrap_or(false) {
+                files.push(entry);
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Compile a Solidity file or read the AST of a compiler output
+fn load(args: &Args, path: &Path) -> Result<SolidityASTOutput, SolidityError> {
+    if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+        let source_root = path.parent().unwrap_or(Path::new(""));
+        return Artifact::new(path, source_root).load();
+    }
+    fs::create_dir_all(&args.bin_dir)?;
+    let mut option = SolidityOption::new(&args.bin_dir, path, SolidityOutputKind::AST);
+    option.remappings = args.remappings.clone();
+    option.base_path = args.base_path.clone();
+    option.include_paths = args.include_paths.clone();
+    match Solidity::new(option).compile()? {
+        SolidityOutput::AST(ast_output) => Ok(ast_output),
+        SolidityOutput::StandardJson(_) => unreachable!(),
+    }
+}
+
+/// Run oracles on selected contracts of a file, contracts in `analyzed` are skipped so that a
+/// contract imported by many files is reported once, a contract which can not be built is reported
+/// as an error without stopping the others
+fn analyze(
+    args: &Args,
+    path: &Path,
+    registry: &Registry,
+    analyzed: &mut HashSet<(String, String)>,
+    report: &mut Report,
+) -> Result<(), Error> {
+    let SolidityASTOutput { ast, sources } = load(args, path)?;
+    let ast_json = json::parse(&ast)
+        .map_err(|error| SolidityError::InvalidArtifact(error.to_string()))?;
+    let dict = Dictionary::new(&ast_json, &sources)?;
+    let mut contracts = dict.filter_by("ContractDefinition").into_iter()
+        .filter(|walker| walker.node.attributes["contractKind"].as_str() != Some("interface"))
+        .filter(|walker| {
+            let name = walker.node.attributes["name"].as_str().unwrap_or("");
+            args.contracts.is_empty() || args.contracts.iter().any(|contract| contract == name)
+        })
+        .collect::<Vec<_>>();
+    contracts.sort_by_key(|walker| (walker.node.file, walker.node.get_range().start));
+    for contract in contracts {
+        let name = contract.node.attributes["name"].as_str().unwrap_or("").to_string();
+        if !analyzed.insert((contract.node.file.to_string(), name.clone())) {
+            continue;
+        }
+        let network = match Network::new(&dict, contract.node.id) {
+            Ok(network) => network,
+            Err(error) => {
+                report.add_error(path, &format!("{}: {}", name, error));
+                continue;
+            },
+        };
+        let mut oracle = Oracle::new(network);
+        if args.format == Format::Dot {
+            report.add_graph(&name, oracle.format());
+        }
+        for finding in oracle.run(registry) {
+            report.add_finding(&name, finding);
+        }
+    }
+    Ok(())
+}
+
+fn run() -> i32 {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            return EXIT_ERROR;
+        },
+    };
+    let mut registry = Registry::with_builtins();
+    if args.help {
+        println!("{}\n\nORACLES:", USAGE);
+        for detector in registry.list() {
+            println!("    {:<30} {}", detector.id(), detector.description());
+        }
+        return EXIT_OK;
+    }
+    if args.version {
+        println!("ssa {}", env!("CARGO_PKG_VERSION"));
+        return EXIT_OK;
+    }
+    if !args.oracles.is_empty() {
+        registry.disable_all();
+    }
+    if let Some(id) = args.oracles.iter().find(|id| !registry.enable(id)) {
+        eprintln!("error: unknown oracle {}", id);
+        return EXIT_ERROR;
+    }
+    let mut report = Report::new(args.format);
+    let mut analyzed = HashSet::new();
+    for path in args.paths.iter() {
+        let mut files = vec![];
+        if let Err(error) = collect_files(path, &mut files) {
+            report.add_error(path, &error);
+        }
+        for file in files {
+            if let Err(error) = analyze(&args, &file, &registry, &mut analyzed, &mut report) {
+                report.add_error(&file, &error);
+            }
+        }
+    }
+    report.print();
+    match (report.has_errors(), report.has_findings()) {
+        (true, _) => EXIT_ERROR,
+        (false, true) => EXIT_FINDINGS,
+        (false, false) => EXIT_OK,
+    }
+}
+
+fn main() {
+    process::exit(run());
+}
@@ -0,0 +1,86 @@
+use std::path::Path;
+use ssa::oracle::Finding;
+use crate::args::Format;
+
+/// Collect results of all files and print them in the chosen format
+pub struct Report {
+    format: Format,
+    findings: Vec<(String, Finding)>,
+    graphs: Vec<(String, String)>,
+    errors: Vec<(String, String)>,
+}
+
+impl Report {
+    pub fn new(format: Format) -> Self {
+        Report { format, findings: vec![], graphs: vec![], errors: vec![] }
+    }
+
+    /// Keep a finding of a contract
+    pub fn add_finding(&mut self, contract: &str, finding: Finding) {
+        self.findings.push((contract.to_string(), finding));
+    }
+
+    /// Keep the DOT graph of a contract
+    pub fn add_graph(&mut self, contract: &str, graph: String) {
+        self.graphs.push((contract.to_string(), graph));
+    }
+
+    pub fn add_error(&mut self, path: &Path, error: &dyn std::fmt::Display) {
+        self.errors.push((path.display().to_string(), error.to_string()));
+    }
+
+    pub fn has_findings(&self) -> bool {
+        !self.findings.is_empty()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Errors always go to stderr except in json format
+    pub fn print(&self) {
+        match self.format {
+            Format::Text => {
+                for (contract, finding) in self.findings.iter() {
+                    let snippet = finding.get_node().get_source().lines().next().unwrap_or("").trim();
+                    println!("{} (contract {})", finding, contract);
+                    println!("    {}", snippet);
+                    for (role, node) in finding.get_related() {
+                        println!("    {} at {}", role, node.get_location());
+                    }
+                }
+                self.print_errors();
+            },
+            Format::Dot => {
+                for (contract, graph) in self.graphs.iter() {
+                    println!("// {}", contract);
+                    println!("{}", graph);
+                }
+                self.print_errors();
+            },
+            Format::Json => {
+                let mut output = json::JsonValue::new_object();
+                output["findings"] = json::JsonValue::new_array();
+                output["errors"] = json::JsonValue::new_array();
+                for (contract, finding) in self.findings.iter() {
+                    let mut value = finding.to_json();
+                    value["contract"] = contract.as_str().into();
+                    output["findings"].push(value).ok();
+                }
+                for (path, message) in self.errors.iter() {
+                    let mut value = json::JsonValue::new_object();
+                    value["path"] = path.as_str().into();
+                    value["message"] = message.as_str().into();
+                    output["errors"].push(value).ok();
+                }
+                println!("{}", output.pretty(2));
+            },
+        }
+    }
+
+    fn print_errors(&self) {
+        for (path, message) in self.errors.iter() {
+            eprintln!("error: {}: {}", path, message);
+        }
+    }
+}
@@ -19,6 +19,8 @@ use crate::core::{
     Vertex,
     Shape,
     Edge,
+    Error,
+    Location,
 };
 
 /// Control Flow Graph
@@ -52,7 +54,7 @@ pub struct LoopBreaker {
 
 impl<'a> ControlFlowGraph<'a> {
     /// Create a new cfg from dictionary
-    pub fn new(dict: &'a Dictionary, contract_id: u32, function_id: u32) -> Self {
+    pub fn new(dict: &'a Dictionary, contract_id: u32, function_id: u32) -> Result<Self, Error> {
         let mut cfg = ControlFlowGraph {
             edges: HashSet::new(),
             vertices: HashSet::new(),
@@ -66,9 +68,9 @@ impl<'a> ControlFlowGraph<'a> {
             stop: 0,
             function_id: 0,
         };
-        cfg.start_at(contract_id, function_id);
+        cfg.start_at(contract_id, function_id)?;
         cfg.update_execution_paths(cfg.start, vec![]);
-        cfg
+        Ok(cfg)
     }
 
     pub fn get_start(&self) -> u32 {
@@ -114,41 +116,53 @@ impl<'a> ControlFlowGraph<'a> {
     /// Traverse comparison nodes in IfStatement, WhileStatement, DoWhileStatement 
     ///
     /// Build a list of nested function calls and connect them toghether
-    pub fn condition_traverse(&mut self, blocks: &Vec<SimpleBlockNode>) -> Vec<u32> {
+    pub fn condition_traverse(&mut self, blocks: &Vec<SimpleBlockNode>) -> Result<Vec<u32>, Error> {
         let mut chains = vec![];
         for block in blocks {
             match block {
                 SimpleBlockNode::FunctionCall(walker)
                 | SimpleBlockNode::IndexAccess(walker) => {
                     let Node { id, source, .. } = walker.node;
-                    let vertice = Vertex::new(id, source, Shape::DoubleCircle);
+                    let vertice = Vertex::new(id, source, Shape::DoubleCircle, walker.get_location());
                     self.vertices.insert(vertice);
                     chains.push(id);
                 },
                 SimpleBlockNode::Unit(walker) => {
                     let Node { id, source, .. } = walker.node;
-                    let vertice = Vertex::new(id, source, Shape::Diamond);
+                    let vertice = Vertex::new(id, source, Shape::Diamond, walker.get_location());
                     self.vertices.insert(vertice);
                     chains.push(id);
                 },
-                _ => unimplemented!(),
+                SimpleBlockNode::Throw(walker)
+                | SimpleBlockNode::Break(walker)
+                | SimpleBlockNode::Continue(walker)
+                | SimpleBlockNode::Require(walker)
+                | SimpleBlockNode::Assert(walker)
+                | SimpleBlockNode::Revert(walker)
+                | SimpleBlockNode::Transfer(walker)
+                | SimpleBlockNode::Suicide(walker)
+                | SimpleBlockNode::Selfdestruct(walker)
+                | SimpleBlockNode::ModifierInvocation(walker) => {
+                    return Err(Error::unsupported_node(walker));
+                },
+                SimpleBlockNode::None => {},
             }
         }
-        for index in 0..chains.len() - 1 {
-            let edge = Edge::new(chains[index], chains[index + 1]);
+        for pair in chains.windows(2) {
+            let edge = Edge::new(pair[0], pair[1]);
             self.edges.insert(edge);
         }
-        chains
+        Ok(chains)
     }
 
     /// Traverse a list of SimpleBlockNode
-    pub fn simple_traverse(&mut self, blocks: &Vec<SimpleBlockNode>, mut predecessors: Vec<u32>, breakers: &mut Vec<LoopBreaker>) -> Vec<u32> {
+    pub fn simple_traverse(&mut self, blocks: &Vec<SimpleBlockNode>, mut predecessors: Vec<u32>, breakers: &mut Vec<LoopBreaker>) -> Result<Vec<u32>, Error> {
         for block in blocks.iter() {
-            if predecessors.is_empty() { return vec![]; }
+            if predecessors.is_empty() { return Ok(vec![]); }
             match block {
                 SimpleBlockNode::Break(walker) => {
                     let Node { id, source, .. } = walker.node;
-                    let vertice = Vertex::new(id, source, Shape::Box);
+                    let vertice = Vertex::new(id, source, Shape::Box, walker.get_location());
                     self.vertices.insert(vertice);
                     for predecessor in predecessors.iter() {
                         let edge = Edge::new(*predecessor, id);
@@ -159,7 +173,7 @@ impl<'a> ControlFlowGraph<'a> {
                 },
                 SimpleBlockNode::Continue(walker) => {
                     let Node { id, source, .. } = walker.node;
-                    let vertice = Vertex::new(id, source, Shape::Box);
+                    let vertice = Vertex::new(id, source, Shape::Box, walker.get_location());
                     self.vertices.insert(vertice);
                     for predecessor in predecessors.iter() {
                         let edge = Edge::new(*predecessor, id);
@@ -172,7 +186,7 @@ impl<'a> ControlFlowGraph<'a> {
                     | SimpleBlockNode::Assert(walker)
                     | SimpleBlockNode::Transfer(walker) => {
                     let Node { id, source, .. } = walker.node;
-                    let vertice = Vertex::new(id, source, Shape::DoubleCircle);
+                    let vertice = Vertex::new(id, source, Shape::DoubleCircle, walker.get_location());
                     self.vertices.insert(vertice);
                     for predecessor in predecessors.iter() {
                         let edge = Edge::new(*predecessor, id);
@@ -184,7 +198,7 @@ impl<'a> ControlFlowGraph<'a> {
                 },
                 SimpleBlockNode::Throw(walker) => {
                     let Node { id, source, .. } = walker.node;
-                    let vertice = Vertex::new(id, source, Shape::Box);
+                    let vertice = Vertex::new(id, source, Shape::Box, walker.get_location());
                     self.vertices.insert(vertice);
                     for predecessor in predecessors.iter() {
                         let edge = Edge::new(*predecessor, id);
@@ -198,7 +212,7 @@ impl<'a> ControlFlowGraph<'a> {
                     | SimpleBlockNode::Selfdestruct(walker)
                     | SimpleBlockNode::Suicide(walker) => {
                     let Node { id, source, .. } = walker.node;
-                    let vertice = Vertex::new(id, source, Shape::DoubleCircle);
+                    let vertice = Vertex::new(id, source, Shape::DoubleCircle, walker.get_location());
                     self.vertices.insert(vertice);
                     for predecessor in predecessors.iter() {
                         let edge = Edge::new(*predecessor, id);
@@ -219,7 +233,7 @@ impl<'a> ControlFlowGraph<'a> {
                         })
                     .collect::<Vec<u32>>();
                     if !predecessors.is_empty() {
-                        let vertice = Vertex::new(id, source, Shape::Box);
+                        let vertice = Vertex::new(id, source, Shape::Box, walker.get_location());
                         self.vertices.insert(vertice);
                     }
                     predecessors.dedup();
@@ -237,46 +251,46 @@ impl<'a> ControlFlowGraph<'a> {
                         })
                     .collect::<Vec<u32>>();
                     if !predecessors.is_empty() {
-                        let vertice = Vertex::new(id, source, Shape::DoubleCircle);
+                        let vertice = Vertex::new(id, source, Shape::DoubleCircle, walker.get_location());
                         self.vertices.insert(vertice);
                     }
                     predecessors.dedup();
                 },
-                SimpleBlockNode::None => unimplemented!(),
+                SimpleBlockNode::None => {},
             }
         }
-        return predecessors;
+        Ok(predecessors)
     }
 
     /// Traverse the whole graph
-    pub fn traverse(&mut self, blocks: &Vec<CodeBlock>, mut predecessors: Vec<u32>, breakers: &mut Vec<LoopBreaker>) -> Vec<u32> {
+    pub fn traverse(&mut self, blocks: &Vec<CodeBlock>, mut predecessors: Vec<u32>, breakers: &mut Vec<LoopBreaker>) -> Result<Vec<u32>, Error> {
         for block in blocks {
-            if predecessors.is_empty() { return vec![]; }
+            if predecessors.is_empty() { return Ok(vec![]); }
             match block {
                 CodeBlock::Block(walker) => {
                     let mut splitter = Splitter::new();
-                    let simple_blocks = splitter.split(walker.clone());
+                    let simple_blocks = splitter.split(walker.clone())?;
                     self.indexes.extend(splitter.get_indexes().clone());
                     self.fcalls.extend(splitter.get_fcalls().clone());
-                    predecessors = self.simple_traverse(&simple_blocks, predecessors.clone(), breakers);
+                    predecessors = self.simple_traverse(&simple_blocks, predecessors.clone(), breakers)?;
                 },
                 CodeBlock::Link(link) => {
                     match &**link {
                         BlockNode::IfStatement(IfStatement { condition, tblocks, fblocks }) => {
                             if let CodeBlock::Block(walker) = condition {
                                 let mut splitter = Splitter::new();
-                                let condition_blocks = splitter.split(walker.clone());
+                                let condition_blocks = splitter.split(walker.clone())?;
                                 self.indexes.extend(splitter.get_indexes().clone());
                                 self.fcalls.extend(splitter.get_fcalls().clone());
-                                let chains = self.condition_traverse(&condition_blocks);
+                                let chains = self.condition_traverse(&condition_blocks)?;
                                 if !chains.is_empty() {
                                     for predecessor in predecessors.iter() {
                                         let edge = Edge::new(*predecessor, chains[0]);
                                         self.edges.insert(edge);
                                     }
                                     predecessors = vec![chains[chains.len() - 1]];
-                                    let mut t = self.traverse(tblocks, predecessors.clone(), breakers);
-                                    let mut f = self.traverse(fblocks, predecessors.clone(), breakers);
+                                    let mut t = self.traverse(tblocks, predecessors.clone(), breakers)?;
+                                    let mut f = self.traverse(fblocks, predecessors.clone(), breakers)?;
                                     predecessors.clear();
                                     predecessors.append(&mut t);
                                     predecessors.append(&mut f);
@@ -286,7 +300,7 @@ impl<'a> ControlFlowGraph<'a> {
                         BlockNode::DoWhileStatement(DoWhileStatement { condition, blocks }) => {
                             if let CodeBlock::Block(walker) = condition {
                                 let mut our_breakers = vec![];
-                                predecessors = self.traverse(blocks, predecessors.clone(), &mut our_breakers);
+                                predecessors = self.traverse(blocks, predecessors.clone(), &mut our_breakers)?;
                                 our_breakers
                                     .iter()
                                     .filter(|breaker| breaker.kind == BreakerType::Continue)
@@ -295,18 +309,18 @@ impl<'a> ControlFlowGraph<'a> {
                                     });
                                 if !predecessors.is_empty() {
                                     let mut splitter = Splitter::new();
-                                    let condition_blocks = splitter.split(walker.clone());
+                                    let condition_blocks = splitter.split(walker.clone())?;
                                     self.indexes.extend(splitter.get_indexes().clone());
                                     self.fcalls.extend(splitter.get_fcalls().clone());
-                                    let chains = self.condition_traverse(&condition_blocks);
+                                    let chains = self.condition_traverse(&condition_blocks)?;
                                     if !chains.is_empty() {
                                         for predecessor in predecessors.iter() {
                                             let edge = Edge::new(*predecessor, chains[0]);
                                             self.edges.insert(edge);
                                         }
+                                        predecessors = vec![chains[chains.len() - 1]];
+                                        self.traverse(blocks, predecessors.clone(), &mut our_breakers)?;
                                     }
-                                    predecessors = vec![chains[chains.len() - 1]];
-                                    self.traverse(blocks, predecessors.clone(), &mut our_breakers);
                                 }
                                 our_breakers
                                     .iter()
@@ -320,17 +334,17 @@ impl<'a> ControlFlowGraph<'a> {
                             if let CodeBlock::Block(walker) = condition {
                                 let mut our_breakers = vec![];
                                 let mut splitter = Splitter::new();
-                                let condition_blocks = splitter.split(walker.clone());
+                                let condition_blocks = splitter.split(walker.clone())?;
                                 self.indexes.extend(splitter.get_indexes().clone());
                                 self.fcalls.extend(splitter.get_fcalls().clone());
-                                let chains = self.condition_traverse(&condition_blocks);
+                                let chains = self.condition_traverse(&condition_blocks)?;
                                 if !chains.is_empty() {
                                     for predecessor in predecessors.iter() {
                                         let edge = Edge::new(*predecessor, chains[0]);
                                         self.edges.insert(edge);
                                     }
                                     predecessors = vec![chains[chains.len() - 1]];
-                                    predecessors = self.traverse(blocks, predecessors.clone(), &mut our_breakers);
+                                    predecessors = self.traverse(blocks, predecessors.clone(), &mut our_breakers)?;
                                     our_breakers
                                         .iter()
                                         .filter(|breaker| breaker.kind == BreakerType::Continue)
@@ -356,18 +370,18 @@ impl<'a> ControlFlowGraph<'a> {
                             let mut cond_predecessors = vec![];
                             if let CodeBlock::Block(walker) = init {
                                 let mut splitter = Splitter::new();
-                                let simple_blocks = splitter.split(walker.clone());
+                                let simple_blocks = splitter.split(walker.clone())?;
                                 self.indexes.extend(splitter.get_indexes().clone());
                                 self.fcalls.extend(splitter.get_fcalls().clone());
-                                predecessors = self.simple_traverse(&simple_blocks, predecessors.clone(), breakers);
+                                predecessors = self.simple_traverse(&simple_blocks, predecessors.clone(), breakers)?;
                             }
                             for _ in 0..2 {
                                 if let CodeBlock::Block(walker) = condition {
                                     let mut splitter = Splitter::new();
-                                    let condition_blocks = splitter.split(walker.clone());
+                                    let condition_blocks = splitter.split(walker.clone())?;
                                     self.indexes.extend(splitter.get_indexes().clone());
                                     self.fcalls.extend(splitter.get_fcalls().clone());
-                                    let chains = self.condition_traverse(&condition_blocks);
+                                    let chains = self.condition_traverse(&condition_blocks)?;
                                     if !chains.is_empty() {
                                         for predecessor in predecessors.iter() {
                                             let edge = Edge::new(*predecessor, chains[0]);
@@ -377,7 +391,7 @@ impl<'a> ControlFlowGraph<'a> {
                                         cond_predecessors = vec![chains[chains.len() - 1]];
                                     }
                                 }
-                                predecessors = self.traverse(blocks, predecessors.clone(), &mut our_breakers);
+                                predecessors = self.traverse(blocks, predecessors.clone(), &mut our_breakers)?;
                                 our_breakers
                                     .iter()
                                     .filter(|breaker| breaker.kind == BreakerType::Continue)
@@ -386,10 +400,10 @@ impl<'a> ControlFlowGraph<'a> {
                                     });
                                 if let CodeBlock::Block(walker) = expression {
                                     let mut splitter = Splitter::new();
-                                    let simple_blocks = splitter.split(walker.clone());
+                                    let simple_blocks = splitter.split(walker.clone())?;
                                     self.indexes.extend(splitter.get_indexes().clone());
                                     self.fcalls.extend(splitter.get_fcalls().clone());
-                                    predecessors = self.simple_traverse(&simple_blocks, predecessors.clone(), breakers);
+                                    predecessors = self.simple_traverse(&simple_blocks, predecessors.clone(), breakers)?;
                                 } 
                             }
                             predecessors = cond_predecessors;
@@ -403,7 +417,7 @@ impl<'a> ControlFlowGraph<'a> {
                         BlockNode::ReturnStatement(ReturnStatement { body }) => {
                             if let CodeBlock::Block(walker) = body {
                                 let mut splitter = Splitter::new();
-                                let simple_blocks = splitter.split(walker.clone());
+                                let simple_blocks = splitter.split(walker.clone())?;
                                 self.indexes.extend(splitter.get_indexes().clone());
                                 self.fcalls.extend(splitter.get_fcalls().clone());
                                 if let Some(returns) = self.returns.get_mut(&self.function_id) {
@@ -411,7 +425,7 @@ impl<'a> ControlFlowGraph<'a> {
                                 } else {
                                     self.returns.insert(self.function_id, vec![walker.node.id]);
                                 }
-                                predecessors = self.simple_traverse(&simple_blocks, predecessors.clone(), breakers);
+                                predecessors = self.simple_traverse(&simple_blocks, predecessors.clone(), breakers)?;
                                 for predecessor in predecessors.iter() {
                                     let edge = Edge::new(*predecessor, self.stop);
                                     self.edges.insert(edge);
@@ -419,40 +433,43 @@ impl<'a> ControlFlowGraph<'a> {
                             }
                             predecessors = vec![];
                         },
-                        BlockNode::Root(_) => unimplemented!(),
-                        BlockNode::None => unimplemented!(),
+                        BlockNode::Root(blocks) => {
+                            predecessors = self.traverse(blocks, predecessors.clone(), breakers)?;
+                        },
+                        BlockNode::None => {},
                     }
                 },
                 CodeBlock::SimpleBlocks(blocks) => {
-                    predecessors = self.simple_traverse(blocks, predecessors.clone(), breakers);
+                    predecessors = self.simple_traverse(blocks, predecessors.clone(), breakers)?;
                 },
-                CodeBlock::None => unimplemented!(), 
+                CodeBlock::None => {},
             }
         }
-        return predecessors;
+        Ok(predecessors)
     }
 
     /// Build a cfg, the cfg starts at FunctionDefinition, ModifierDefinition `entry_id`
-    pub fn start_at(&mut self, contract_id: u32, function_id: u32) {
+    pub fn start_at(&mut self, contract_id: u32, function_id: u32) -> Result<(), Error> {
         self.start = function_id * 100000;
         self.stop = self.start + 1;
         self.function_id = function_id;
-        let mut graph = Graph::new(self.dict.walker_at(function_id).unwrap().clone());
-        let root = graph.update();
+        let walker = self.dict.walker_at(function_id).ok_or(Error::UnknownFunction(function_id))?;
+        let mut graph = Graph::new(walker.clone());
+        let root = graph.update()?;
         let states = self.dict.find_walkers(SmartContractQuery::StatesByContractId(contract_id));
         if let BlockNode::Root(blocks) = root {
             for id in vec![self.start, self.stop] {
-                let vertex = Vertex::new(id, "", Shape::Point);
+                let vertex = Vertex::new(id, "", Shape::Point, Location::default());
                 self.vertices.insert(vertex);
             }
             let last_id = states.iter().fold(self.start, |prev, cur| {
-                let vertex = Vertex::new(cur.node.id, cur.node.source, Shape::Box);
+                let vertex = Vertex::new(cur.node.id, cur.node.source, Shape::Box, cur.get_location());
                 let edge = Edge::new(prev, cur.node.id);
                 self.vertices.insert(vertex);
                 self.edges.insert(edge);
                 cur.node.id
             });
-            let predecessors = self.traverse(blocks, vec![last_id], &mut vec![]);
+            let predecessors = self.traverse(blocks, vec![last_id], &mut vec![])?;
             for predecessor in predecessors.iter() {
                 let edge = Edge::new(*predecessor, self.stop);
                 self.edges.insert(edge);
@@ -460,6 +477,7 @@ impl<'a> ControlFlowGraph<'a> {
         }
         let parameters = graph.get_parameters().clone();
         self.parameters.insert(function_id, parameters);
+        Ok(())
     }
 
     fn update_execution_paths(&mut self, from: u32, mut execution_path: Vec<u32>) {
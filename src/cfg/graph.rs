@@ -1,4 +1,4 @@
-use crate::core::Walker;
+use crate::core::{ Walker, Error };
 use crate::cfg::{
     CodeBlock,
     BlockNode,
@@ -68,26 +68,26 @@ impl<'a> Graph<'a> {
 
     /// Traverse the body of a function based on token kind, for some special token call build_node
     /// to find it's components
-    pub fn build_items(&mut self, walker: Walker<'a>) -> Vec<CodeBlock<'a>> {
-        match walker.node.name {
+    pub fn build_items(&mut self, walker: Walker<'a>) -> Result<Vec<CodeBlock<'a>>, Error> {
+        let items = match walker.node.name {
             "IfStatement" => {
-                let node = self.build_node(NodeKind::IfStatement, walker); 
+                let node = self.build_node(NodeKind::IfStatement, walker)?; 
                 vec![CodeBlock::Link(Box::new(node))]
             },
             "WhileStatement" => {
-                let node = self.build_node(NodeKind::WhileStatement, walker);
+                let node = self.build_node(NodeKind::WhileStatement, walker)?;
                 vec![CodeBlock::Link(Box::new(node))]
             },
             "ForStatement" => {
-                let node = self.build_node(NodeKind::ForStatement, walker);
+                let node = self.build_node(NodeKind::ForStatement, walker)?;
                 vec![CodeBlock::Link(Box::new(node))]
             },
             "DoWhileStatement" => {
-                let node = self.build_node(NodeKind::DoWhileStatement, walker);
+                let node = self.build_node(NodeKind::DoWhileStatement, walker)?;
                 vec![CodeBlock::Link(Box::new(node))]
             },
             "Return" => {
-                let node = self.build_node(NodeKind::ReturnStatement, walker);
+                let node = self.build_node(NodeKind::ReturnStatement, walker)?;
                 vec![CodeBlock::Link(Box::new(node))]
             },
             "Throw" => {
@@ -110,25 +110,26 @@ impl<'a> Graph<'a> {
                 vec![CodeBlock::Block(walker)]
             },
             _ => vec![CodeBlock::Block(walker)],
-        }
+        };
+        Ok(items)
     }
 
     /// Traverse parameter list and modifier invocations, call build_items to traverse body of a
     /// function
-    pub fn build_block(&mut self, kind: BlockKind, walker: Walker<'a>) -> Vec<CodeBlock<'a>> {
+    pub fn build_block(&mut self, kind: BlockKind, walker: Walker<'a>) -> Result<Vec<CodeBlock<'a>>, Error> {
         let mut blocks = vec![];
         match kind {
             BlockKind::Body => {
-                for walker in walker.direct_childs(|_| true) {
-                    blocks.append(&mut self.build_items(walker));
+                for walker in walker.direct_childs(|_| true)? {
+                    blocks.append(&mut self.build_items(walker)?);
                 }
             },
             BlockKind::Param => {
-                for (index, walker) in walker.direct_childs(|_| true).into_iter().enumerate() {
+                for (index, walker) in walker.direct_childs(|_| true)?.into_iter().enumerate() {
                     match walker.node.name {
                         "ParameterList" => {
                             if index == 0 {
-                                for walker in walker.direct_childs(|_| true) {
+                                for walker in walker.direct_childs(|_| true)? {
                                     self.parameters.push(walker.node.id);
                                     let block = CodeBlock::Block(walker);
                                     blocks.push(block);
@@ -136,7 +137,7 @@ impl<'a> Graph<'a> {
                             }
                         },
                         "Block" => {
-                            blocks.append(&mut self.build_block(BlockKind::Body, walker));
+                            blocks.append(&mut self.build_block(BlockKind::Body, walker)?);
                         },
                         "ModifierInvocation" => {
                             blocks.push(CodeBlock::Block(walker));
@@ -146,14 +147,14 @@ impl<'a> Graph<'a> {
                 }
             },
         }
-        blocks
-    } 
+        Ok(blocks)
+    }
 
     /// For each node, try to detect it's components
-    pub fn build_node(&mut self, kind: NodeKind, walker: Walker<'a>) -> BlockNode<'a> {
-        match kind {
+    pub fn build_node(&mut self, kind: NodeKind, walker: Walker<'a>) -> Result<BlockNode<'a>, Error> {
+        let node = match kind {
             NodeKind::Root => {
-                BlockNode::Root(self.build_block(BlockKind::Param, walker))
+                BlockNode::Root(self.build_block(BlockKind::Param, walker)?)
             },
             NodeKind::ForStatement => {
                 let mut blocks = vec![];
@@ -161,14 +162,18 @@ impl<'a> Graph<'a> {
                 let mut init = CodeBlock::None;
                 let mut expression = CodeBlock::None;
                 let mut props = vec!["initializationExpression", "condition", "loopExpression", "body"];
-                for (key, _) in walker.node.attributes.entries() {
+                for key in walker.node.attributes.null_keys() {
                     props = props.iter().filter_map(|x| {
                         if x == &key { return None; }
                         Some(*x)
                     }).collect();
                 }
-                for (index, walker) in walker.direct_childs(|_| true).into_iter().enumerate() {
-                    match props[index] {
+                for (index, walker) in walker.direct_childs(|_| true)?.into_iter().enumerate() {
+                    let prop = match props.get(index) {
+                        Some(prop) => *prop,
+                        None => return Err(Error::unsupported_node(&walker)),
+                    };
+                    match prop {
                         "initializationExpression" => {
                             init = CodeBlock::Block(walker);
                         },
@@ -180,9 +185,9 @@ impl<'a> Graph<'a> {
                         },
                         _ => {
                             if walker.node.name == "Block" {
-                                blocks = self.build_block(BlockKind::Body, walker);
+                                blocks = self.build_block(BlockKind::Body, walker)?;
                             } else {
-                                blocks.append(&mut self.build_items(walker));
+                                blocks.append(&mut self.build_items(walker)?);
                             }
                         },
                     }
@@ -192,20 +197,20 @@ impl<'a> Graph<'a> {
             NodeKind::DoWhileStatement => {
                 let mut condition = CodeBlock::None; 
                 let mut blocks = vec![];
-                for (index, walker) in walker.direct_childs(|_| true).into_iter().enumerate() {
+                for (index, walker) in walker.direct_childs(|_| true)?.into_iter().enumerate() {
                     match index {
                         0 => {
                             condition = CodeBlock::Block(walker);
                         },
                         1 => match walker.node.name {
                             "Block" => {
-                                blocks = self.build_block(BlockKind::Body, walker);
+                                blocks = self.build_block(BlockKind::Body, walker)?;
                             },
                             _ => {
-                                blocks.append(&mut self.build_items(walker));
+                                blocks.append(&mut self.build_items(walker)?);
                             }
                         },
-                        _ => unimplemented!(),
+                        _ => return Err(Error::unsupported_node(&walker)),
                     }
                 }
                 BlockNode::DoWhileStatement(DoWhileStatement { condition, blocks })
@@ -213,20 +218,20 @@ impl<'a> Graph<'a> {
             NodeKind::WhileStatement => {
                 let mut condition = CodeBlock::None; 
                 let mut blocks = vec![];
-                for (index, walker) in walker.direct_childs(|_| true).into_iter().enumerate() {
+                for (index, walker) in walker.direct_childs(|_| true)?.into_iter().enumerate() {
                     match index {
                         0 => {
                             condition = CodeBlock::Block(walker);
                         },
                         1 => match walker.node.name {
                             "Block" => {
-                                blocks = self.build_block(BlockKind::Body, walker);
+                                blocks = self.build_block(BlockKind::Body, walker)?;
                             },
                             _ => {
-                                blocks.append(&mut self.build_items(walker));
+                                blocks.append(&mut self.build_items(walker)?);
                             }
                         },
-                        _ => unimplemented!(),
+                        _ => return Err(Error::unsupported_node(&walker)),
                     }
                 }
                 BlockNode::WhileStatement(WhileStatement { condition, blocks })
@@ -235,28 +240,28 @@ impl<'a> Graph<'a> {
                 let mut condition = CodeBlock::None; 
                 let mut tblocks = vec![];
                 let mut fblocks = vec![];
-                for (index, walker) in walker.direct_childs(|_| true).into_iter().enumerate() {
+                for (index, walker) in walker.direct_childs(|_| true)?.into_iter().enumerate() {
                     match index {
                         0 => {
                             condition = CodeBlock::Block(walker);
                         },
                         1 => match walker.node.name {
                             "Block" => {
-                                tblocks = self.build_block(BlockKind::Body, walker);
+                                tblocks = self.build_block(BlockKind::Body, walker)?;
                             },
                             _ => {
-                                tblocks.append(&mut self.build_items(walker));
+                                tblocks.append(&mut self.build_items(walker)?);
                             }
                         },
                         2 => match walker.node.name {
                             "Block" => {
-                                fblocks = self.build_block(BlockKind::Body, walker);
+                                fblocks = self.build_block(BlockKind::Body, walker)?;
                             },
                             _ => {
-                                fblocks.append(&mut self.build_items(walker));
+                                fblocks.append(&mut self.build_items(walker)?);
                             }
                         },
-                        _ => unimplemented!(),
+                        _ => return Err(Error::unsupported_node(&walker)),
                     }
                 }
                 BlockNode::IfStatement(IfStatement { condition, tblocks, fblocks })
@@ -265,16 +270,14 @@ impl<'a> Graph<'a> {
                 let body = CodeBlock::Block(walker);
                 BlockNode::ReturnStatement(ReturnStatement { body })
             },
-        } 
+        };
+        Ok(node)
     }
 
-    pub fn update(&mut self) -> &BlockNode {
-        match self.root {
-            BlockNode::None => {
-                self.root = self.build_node(NodeKind::Root, self.walker.clone());
-                &self.root
-            },
-            _ => &self.root,
+    pub fn update(&mut self) -> Result<&BlockNode, Error> {
+        if let BlockNode::None = self.root {
+            self.root = self.build_node(NodeKind::Root, self.walker.clone())?;
         }
+        Ok(&self.root)
     }
 }
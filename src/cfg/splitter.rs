@@ -1,5 +1,5 @@
 use crate::cfg::SimpleBlockNode;
-use crate::core::Walker;
+use crate::core::{ Walker, Error };
 use std::collections::HashMap;
 
 pub struct Splitter {
@@ -23,7 +23,7 @@ impl Splitter {
         &self.fcalls
     }
 
-    pub fn split<'a>(&mut self, walker: Walker<'a>) -> Vec<SimpleBlockNode<'a>> {
+    pub fn split<'a>(&mut self, walker: Walker<'a>) -> Result<Vec<SimpleBlockNode<'a>>, Error> {
         let mut function_calls = vec![];
         let ig = |_: &Walker, _: &Vec<Walker>| false;
         let fi = |walker: &Walker, _: &Vec<Walker>| {
@@ -33,11 +33,11 @@ impl Splitter {
         };
         let vertex_id = walker.node.id;
         // Split parameters to other nodes
-        for walker in walker.walk(true, ig, fi).into_iter() {
+        for walker in walker.walk(true, ig, fi)?.into_iter() {
             let mut parameters = vec![];
-            for walker in walker.direct_childs(|_| true).into_iter() {
+            for walker in walker.direct_childs(|_| true)?.into_iter() {
                 parameters.push(walker.node.id);
-                function_calls.append(&mut self.split(walker));
+                function_calls.append(&mut self.split(walker)?);
             }
             match walker.node.name {
                 "IndexAccess" => {
@@ -52,12 +52,13 @@ impl Splitter {
             }
             match walker.node.name {
                 "FunctionCall" => {
-                    walker.direct_childs(|_| true).get(0)
-                        .and_then(|walker| {
+                    walker.direct_childs(|_| true)?.get(0)
+                        .map(|walker| {
                             let function_name = walker.node.attributes["value"].as_str();
                             let member_name = walker.node.attributes["member_name"].as_str();
                             let reference = walker.node.attributes["referencedDeclaration"].as_u32();
-                            function_name.or(member_name).and_then(|name| Some((name, reference)))
+                            // The callee of `a.call.value(1)()` is a FunctionCall which has no name
+                            (function_name.or(member_name).unwrap_or(""), reference)
                         })
                         .map(|identity| match identity {
                             ("revert", _) => {
@@ -109,7 +110,7 @@ impl Splitter {
             let node = SimpleBlockNode::Unit(walker.clone());
             function_calls.push(node);
         }
-        function_calls
+        Ok(function_calls)
     }
 
 }
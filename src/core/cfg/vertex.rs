@@ -1,3 +1,5 @@
+use crate::core::Location;
+
 /// Shape represents function of a node in CFG 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum Shape {
@@ -13,14 +15,16 @@ pub struct Vertex {
     id: u32,
     source: String,
     shape: Shape,
+    location: Location,
 }
 
 impl Vertex {
-    pub fn new(id: u32, source: &str, shape: Shape) -> Self {
+    pub fn new(id: u32, source: &str, shape: Shape, location: Location) -> Self {
         Vertex {
             id,
             shape,
             source: source.to_string(),
+            location,
         }
     }
 
@@ -35,4 +39,9 @@ impl Vertex {
     pub fn get_shape(&self) -> &Shape {
         &self.shape
     }
+
+    /// Location of the node, start and stop points have an empty location
+    pub fn get_location(&self) -> &Location {
+        &self.location
+    }
 }
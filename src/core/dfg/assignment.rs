@@ -4,6 +4,7 @@ use crate::core::{
     Walker,
     Dictionary,
     Operator,
+    Error,
 };
 
 
@@ -69,7 +70,7 @@ impl Assignment {
     }
 
     /// Find all variables in current walker, the dictionary is used to identify global variables 
-    pub fn parse(walker: &Walker, dict: &Dictionary) -> Vec<Assignment> {
+    pub fn parse(walker: &Walker, dict: &Dictionary) -> Result<Vec<Assignment>, Error> {
         let mut assignments = vec![];
         let fi = |walker: &Walker, _: &Vec<Walker>| {
             walker.node.name == "Assignment"
@@ -83,7 +84,7 @@ impl Assignment {
             || walker.node.name == "Identifier"
             || walker.node.name == "IndexAccess"
         };
-        for walker in walker.walk(false, ig, fi).into_iter() {
+        for walker in walker.walk(false, ig, fi)?.into_iter() {
             let operator = walker.node.attributes["operator"].as_str();
             let op = match operator {
                 Some(op) => match op {
@@ -94,14 +95,14 @@ impl Assignment {
             };
             let mut lhs = HashSet::new();
             let mut rhs = HashSet::new();
-            let walkers = walker.direct_childs(|_| true);
-            lhs.extend(Variable::parse(&walkers[0], dict));
+            let walkers = walker.direct_childs(|_| true)?;
+            lhs.extend(Variable::parse(&walkers[0], dict)?);
             if walkers.len() >= 2 {
-                rhs.extend(Variable::parse(&walkers[1], dict));
+                rhs.extend(Variable::parse(&walkers[1], dict)?);
             }
             assignments.push(Assignment { lhs, rhs, op });
         }
-        assignments
+        Ok(assignments)
     }
 
 }
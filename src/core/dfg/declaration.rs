@@ -6,6 +6,7 @@ use crate::core::{
     Operator,
     Assignment,
     FlatVariable,
+    Error,
 };
 
 #[derive(Debug)]
@@ -19,7 +20,7 @@ impl Declaration {
         &self.assignment
     }
     /// Find all variables in current walker, the dictionary is used to identify global variables 
-    pub fn parse(walker: &Walker, dict: &Dictionary) -> Vec<Declaration> {
+    pub fn parse(walker: &Walker, dict: &Dictionary) -> Result<Vec<Declaration>, Error> {
         let mut declarations = vec![];
         let fi = |walker: &Walker, path: &Vec<Walker>| {
             match walker.node.name {
@@ -38,18 +39,18 @@ impl Declaration {
             || walker.node.name == "IndexAccess"
             || walker.node.name == "Assignment"
         };
-        for walker in walker.walk(false, ig, fi).into_iter() {
+        for walker in walker.walk(false, ig, fi)?.into_iter() {
             let op = Operator::Equal;
-            let walkers = walker.direct_childs(|_| true);
-            let flat_variable = FlatVariable::new(&walker, dict);
+            let walkers = walker.direct_childs(|_| true)?;
+            let flat_variable = FlatVariable::new(&walker, dict)?;
             let lhs = flat_variable.get_variables();
             let mut rhs = HashSet::new();
             if walkers.len() >= 2 {
-                rhs.extend(Variable::parse(&walkers[1], dict));
+                rhs.extend(Variable::parse(&walkers[1], dict)?);
             }
             let assignment = Assignment::new(lhs, rhs, op);
             declarations.push(Declaration { assignment });
         }
-        declarations
+        Ok(declarations)
     }
 }
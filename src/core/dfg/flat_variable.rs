@@ -9,44 +9,42 @@ use crate::core::{
     Walker,
     Utils,
     Variable,
+    TraceEvent,
+    Error,
 };
 
 pub struct FlatVariable<'a> {
+    id: u32,
     dict: &'a Dictionary<'a>,
     flats: Vec<(Vec<Member>, String, String)>,
     attributes: Vec<String>,
 }
 
 impl<'a> FlatVariable<'a> {
-    pub fn new(walker: &Walker, dict: &'a Dictionary) -> Self {
-        let mut flat_variable = FlatVariable { dict, flats: vec![], attributes: vec![] };
-        let root_walker = Utils::find_root_walker(walker, dict);
+    pub fn new(walker: &Walker, dict: &'a Dictionary) -> Result<Self, Error> {
+        let mut flat_variable = FlatVariable { id: walker.node.id, dict, flats: vec![], attributes: vec![] };
+        let root_walker = Utils::find_root_walker(walker, dict)?;
         let declaration = root_walker.node.attributes["referencedDeclaration"].as_u32();
         let mut members = vec![];
         let mut attributes = vec![];
         let attribute = root_walker.node.attributes["value"].as_str()
-            .or(root_walker.node.attributes["name"].as_str()).unwrap();
+            .or(root_walker.node.attributes["name"].as_str()).unwrap_or("");
         attributes.push(attribute.to_string());
         if root_walker.node.name == "VariableDeclaration" {
             members.push(Member::Reference(root_walker.node.id));
         } else {
-            if declaration.and_then(|declaration| dict.walker_at(declaration)).is_none() {
-                members.push(Member::Global(attribute.to_string()));
-            } else {
-                members.push(Member::Reference(declaration.unwrap()));
+            match declaration.filter(|declaration| dict.walker_at(*declaration).is_some()) {
+                Some(declaration) => members.push(Member::Reference(declaration)),
+                None => members.push(Member::Global(attribute.to_string())),
             }
         }
-        flat_variable.update_flats(&Utils::normalize_kind(&root_walker), members, attributes);
-        flat_variable.update_attributes(walker, dict);
-        flat_variable
+        flat_variable.update_flats(&Utils::normalize_kind(&root_walker), members, attributes)?;
+        flat_variable.update_attributes(walker, dict)?;
+        Ok(flat_variable)
     }
 
     pub fn get_variables(&self) -> HashSet<Variable> {
         let mut ret = HashSet::new();
-        println!("Found: {:?}", self.attributes);
-        for flat in self.flats.iter() {
-            println!("\t{:?}", flat);
-        }
         for (members, attributes, kind) in self.flats.iter() {
             let joined_1 = self.attributes.join(".");
             let joined_2 = format!("{}.", joined_1);
@@ -60,48 +58,46 @@ impl<'a> FlatVariable<'a> {
                 ret.insert(variable);
             }
         }
-        if ret.is_empty() {
-            println!("ERROR on decode variables");
-        }
-        println!("==> Flatted Varariable <==");
-        for r in ret.iter() {
-            println!("\t{:?}", r);
-        }
+        self.dict.get_tracer().record(|| TraceEvent::FlatVariable {
+            id: self.id,
+            attributes: self.attributes.clone(),
+            variables: ret.iter().cloned().collect(),
+        });
         ret
     } 
 
-    fn update_attributes(&mut self, walker: &Walker, dict: &Dictionary) {
+    fn update_attributes(&mut self, walker: &Walker, dict: &Dictionary) -> Result<(), Error> {
         match walker.node.name {
             "IndexAccess" => {
                 self.attributes.insert(0, "$".to_string());
             },
             "MemberAccess" => {
-                let member_name = walker.node.attributes["member_name"].as_str().unwrap();
+                let member_name = walker.node.attributes["member_name"].as_str().unwrap_or("");
                 self.attributes.insert(0, member_name.to_string());
             },
             "Identifier" => {
-                let value = walker.node.attributes["value"].as_str().unwrap();
+                let value = walker.node.attributes["value"].as_str().unwrap_or("");
                 self.attributes.insert(0, value.to_string());
             },
             "VariableDeclaration" => {
-                let name = walker.node.attributes["name"].as_str().unwrap();
+                let name = walker.node.attributes["name"].as_str().unwrap_or("");
                 self.attributes.insert(0, name.to_string());
             },
             "FunctionCall" => {
                 let type_conversion = walker.node.attributes["type_conversion"].as_bool().unwrap_or(false);
-                if type_conversion {
-                    let to_kind = walker.node.attributes["type"].as_str().unwrap();
+                if let (true, Some(to_kind)) = (type_conversion, walker.node.attributes["type"].as_str()) {
                     self.attributes.insert(0, to_kind.to_string());
                 }
             },
             _ => {}
         }
-        walker.direct_childs(|_| true).get(0).map(|walker| {
-            self.update_attributes(walker, dict);
-        });
+        match walker.direct_childs(|_| true)?.get(0) {
+            Some(walker) => self.update_attributes(walker, dict),
+            None => Ok(()),
+        }
     }
 
-    fn update_flats(&mut self, kind: &str, mut members: Vec<Member>, mut attributes: Vec<String>) {
+    fn update_flats(&mut self, kind: &str, mut members: Vec<Member>, mut attributes: Vec<String>) -> Result<(), Error> {
         let struct_regex = Regex::new(r"^struct ([^\[\]]*)((\[\])*)").unwrap();
         let mapping_regex = Regex::new(r"^mapping\(.+\)((\[\])*)").unwrap();
         let contract_regex = Regex::new(r"^contract ([^\[\]]*)((\[\])*)").unwrap();
@@ -123,16 +119,16 @@ impl<'a> FlatVariable<'a> {
                         members.push(Member::IndexAccess);
                         attributes.push(String::from("$"));
                     }
-                    self.dict.find_walkers(SmartContractQuery::StructByName(struct_kind)).get(0).map(|walker| {
-                        for walker in walker.direct_childs(|_| true) {
+                    if let Some(walker) = self.dict.find_walkers(SmartContractQuery::StructByName(struct_kind)).get(0) {
+                        for walker in walker.direct_childs(|_| true)? {
                             let mut members = members.clone();
                             let mut attributes = attributes.clone();
                             let name = walker.node.attributes["name"].as_str().unwrap_or("");
                             members.push(Member::Reference(walker.node.id));
                             attributes.push(name.to_string());
-                            self.update_flats(&Utils::normalize_kind(&walker), members, attributes);
+                            self.update_flats(&Utils::normalize_kind(&walker), members, attributes)?;
                         }
-                    });
+                    }
                 }
             },
             (_, true, _, _, _) => {
@@ -158,7 +154,7 @@ impl<'a> FlatVariable<'a> {
                         members.push(Member::IndexAccess);
                         attributes.push(String::from("$"));
                     }
-                    self.update_flats(&mapping_kind.trim(), members.clone(), attributes.clone());
+                    self.update_flats(&mapping_kind.trim(), members.clone(), attributes.clone())?;
                 }
             },
             (_, _, true, _, _) => {
@@ -169,13 +165,13 @@ impl<'a> FlatVariable<'a> {
                         members.push(Member::IndexAccess);
                         attributes.push(String::from("$"));
                     }
-                    self.dict.find_ids(SmartContractQuery::ContractByName(contract_kind)).get(0).map(|contract_id| {
+                    if let Some(contract_id) = self.dict.find_ids(SmartContractQuery::ContractByName(contract_kind)).get(0) {
                         {
                             let mut members = members.clone();
                             let mut attributes = attributes.clone();
                             members.push(Member::Global("balance".to_string()));
                             attributes.push("balance".to_string());
-                            self.update_flats("uint", members, attributes);
+                            self.update_flats("uint", members, attributes)?;
                         }
                         for walker in self.dict.find_walkers(SmartContractQuery::StatesByContractId(*contract_id)) {
                             let mut members = members.clone();
@@ -183,7 +179,7 @@ impl<'a> FlatVariable<'a> {
                             let name = walker.node.attributes["name"].as_str().unwrap_or("");
                             members.push(Member::Reference(walker.node.id));
                             attributes.push(name.to_string());
-                            self.update_flats(&Utils::normalize_kind(&walker), members, attributes);
+                            self.update_flats(&Utils::normalize_kind(&walker), members, attributes)?;
                         }
                         for walker in self.dict.find_walkers(SmartContractQuery::FunctionsByContractId(*contract_id)) {
                             let mut members = members.clone();
@@ -191,17 +187,17 @@ impl<'a> FlatVariable<'a> {
                             let name = walker.node.attributes["name"].as_str().unwrap_or("");
                             members.push(Member::Reference(walker.node.id));
                             attributes.push(name.to_string());
-                            walker.direct_childs(|_| true).get(1).map(|walker| {
-                                let walkers = walker.direct_childs(|_| true);
+                            if let Some(walker) = walker.direct_childs(|_| true)?.get(1) {
+                                let walkers = walker.direct_childs(|_| true)?;
                                 if walkers.is_empty() {
-                                    self.update_flats("void", members, attributes);
+                                    self.update_flats("void", members, attributes)?;
                                 } else {
                                     let walker = &walkers[0];
-                                    self.update_flats(&Utils::normalize_kind(&walker), members, attributes);
+                                    self.update_flats(&Utils::normalize_kind(&walker), members, attributes)?;
                                 }
-                            });
+                            }
                         }
-                    });
+                    }
                 } 
             },
             (_, _, _, true, _) => {
@@ -220,14 +216,14 @@ impl<'a> FlatVariable<'a> {
                         let mut attributes = attributes.clone();
                         members.push(prop.0);
                         attributes.push(prop.1);
-                        self.update_flats(&prop.2, members, attributes);
+                        self.update_flats(&prop.2, members, attributes)?;
                     }
                 }
             },
             (_, _, _, _, true) => {
                 for cap in conversion_regex.captures_iter(kind) {
                     let inner_kind = (&cap[1]).to_string();
-                    self.update_flats(&inner_kind, members.clone(), attributes.clone());
+                    self.update_flats(&inner_kind, members.clone(), attributes.clone())?;
                 }
             },
             _ => {
@@ -272,26 +268,26 @@ impl<'a> FlatVariable<'a> {
                         let mut attributes = attributes.clone();
                         members.push(Member::Global(prop.0.to_string()));
                         attributes.push(prop.0.to_string());
-                        self.update_flats(prop.1, members, attributes);
+                        self.update_flats(prop.1, members, attributes)?;
                     }
                 } else {
                     let query = SmartContractQuery::LibraryByKind(kind.to_string());
                     if let Some(walker) = self.dict.find_walkers(query).get(0) {
-                        for walker in walker.direct_childs(|_| true) {
+                        for walker in walker.direct_childs(|_| true)? {
                             let mut members = members.clone();
                             let mut attributes = attributes.clone();
                             let name = walker.node.attributes["name"].as_str().unwrap_or("");
                             members.push(Member::Reference(walker.node.id));
                             attributes.push(name.to_string());
-                            walker.direct_childs(|_| true).get(1).map(|walker| {
-                                let walkers = walker.direct_childs(|_| true);
+                            if let Some(walker) = walker.direct_childs(|_| true)?.get(1) {
+                                let walkers = walker.direct_childs(|_| true)?;
                                 let kind = match walkers.is_empty() {
                                     true => String::from("void"),
                                     false => Utils::normalize_kind(&walkers[0]).to_string(),
                                 };
                                 let flat = (members, attributes.join("."), kind);
                                 self.flats.push(flat);
-                            });
+                            }
                         }
                     } else {
                         let flat = (members, attributes.join("."), kind.to_string());
@@ -300,5 +296,6 @@ impl<'a> FlatVariable<'a> {
                 }
             }
         }
+        Ok(())
     }
 }
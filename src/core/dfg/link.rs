@@ -1,7 +1,7 @@
 use crate::core::Variable;
 
 /// Data dependency link between to node
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DataLink {
     from: (Variable, u32),
     to: (Variable, u32),
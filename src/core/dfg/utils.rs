@@ -2,16 +2,20 @@ pub mod Utils {
     extern crate regex;
     use crate::core::Walker;
     use crate::core::Dictionary;
+    use crate::core::Error;
     use regex::Regex;
 
-    pub fn find_root_walker<'a>(walker: &'a Walker, dict: &'a Dictionary) -> Walker<'a> {
-        if walker.direct_childs(|_| true).is_empty() || walker.node.name == "VariableDeclaration" {
-            walker.clone()
+    pub fn find_root_walker<'a>(walker: &'a Walker, dict: &'a Dictionary) -> Result<Walker<'a>, Error> {
+        let children = walker.direct_childs(|_| true)?;
+        if children.is_empty() || walker.node.name == "VariableDeclaration" {
+            Ok(walker.clone())
         } else {
-            let walker = walker.direct_childs(|_| true).get(0)
-                .and_then(|walker| dict.walker_at(walker.node.id))
-                .unwrap();
-            find_root_walker(&walker, dict)
+            let child = children.get(0)
+                .and_then(|walker| dict.walker_at(walker.node.id));
+            match child {
+                Some(child) => find_root_walker(child, dict),
+                None => Ok(walker.clone()),
+            }
         }
     }
 
@@ -35,4 +39,16 @@ pub mod Utils {
         return norm_type;
     } 
 
+    /// Data location which normalize_kind strips, e.g `storage pointer` of `struct A.S storage
+    /// pointer`. The location of the outermost type is returned, it is empty for value types
+    pub fn find_location(walker: &Walker) -> String {
+        let type_str = walker.node.attributes["type"].as_str().unwrap_or("");
+        let words = type_str.split_whitespace().collect::<Vec<&str>>();
+        let locations = ["storage", "memory", "calldata"];
+        match words.iter().rposition(|word| locations.contains(word)) {
+            Some(index) => words[index..].join(" "),
+            None => String::new(),
+        }
+    }
+
 }
@@ -7,6 +7,7 @@ use crate::core::{
     VariableComparison,
     FlatVariable,
     DataLink,
+    Error,
 };
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
@@ -39,7 +40,7 @@ impl Variable {
         &self.unflat
     }
 
-    pub fn parse(walker: &Walker, dict: &Dictionary) -> HashSet<Self> {
+    pub fn parse(walker: &Walker, dict: &Dictionary) -> Result<HashSet<Self>, Error> {
         let mut ret = HashSet::new();
         let fi = |walker: &Walker, _: &Vec<Walker>| {
             walker.node.name == "MemberAccess"
@@ -52,11 +53,11 @@ impl Variable {
             || walker.node.name == "VariableDeclarationStatement"
             || walker.node.name == "Assignment"
         };
-        for walker in walker.walk(true, ig, fi) {
-            let flat_variable = FlatVariable::new(&walker, dict);
+        for walker in walker.walk(true, ig, fi)? {
+            let flat_variable = FlatVariable::new(&walker, dict)?;
             ret.extend(flat_variable.get_variables());
         }
-        ret
+        Ok(ret)
     }
 
     pub fn equal_property(&self, other: &Variable) -> bool {
@@ -1,6 +1,9 @@
 use crate::core::Walker;
 use crate::core::SmartContract;
 use crate::core::SmartContractQuery;
+use crate::core::Error;
+use crate::core::Tracer;
+use crate::loader::SolidityError;
 use std::collections::HashMap;
 
 /// Allow searching by node id 
@@ -8,32 +11,48 @@ use std::collections::HashMap;
 pub struct Dictionary<'a> {
     entries: HashMap<u32, Walker<'a>>,
     smart_contract: SmartContract,
+    tracer: Tracer,
 }
 
 impl<'a> Dictionary<'a> {
-    /// Create new dictionary
-    pub fn new(value: &'a json::JsonValue, sources: &'a HashMap<String, String>) -> Self {
+    /// Create new dictionary, fail if a node of the AST is malformed
+    pub fn new(value: &'a json::JsonValue, sources: &'a HashMap<String, String>) -> Result<Self, Error> {
         let mut dict = Dictionary {
             entries: HashMap::new(),
             smart_contract: SmartContract::new(), 
+            tracer: Tracer::new(),
         };
         for (name, source) in sources {
-            let ast_one = &value["sources"][name]["AST"];
-            let walker = Walker::new(ast_one, source);
-            dict.traverse(&walker);
+            // combined-json keeps the AST in `AST`, standard-json keeps it in `legacyAST` or `ast`
+            let ast_one = vec!["AST", "legacyAST", "ast"].into_iter()
+                .map(|key| &value["sources"][name][key])
+                .find(|ast_one| !ast_one.is_null())
+                .ok_or_else(|| SolidityError::InvalidArtifact(format!("{} has no AST", name)))?;
+            let walker = Walker::new(ast_one, source, name)?;
+            dict.traverse(&walker)?;
         }
         let contract_walkers = dict.entries.iter().map(|(_, walker)| walker)
             .filter(|walker| walker.node.name == "ContractDefinition")
             .collect::<Vec<&Walker>>();
-        dict.smart_contract.update(contract_walkers);
-        dict
+        dict.smart_contract.update(contract_walkers)?;
+        Ok(dict)
     }
 
-    fn traverse(&mut self, walker: &Walker<'a>) {
-        for walker in walker.direct_childs(|_| true).into_iter() {
-            self.traverse(&walker);
+    fn traverse(&mut self, walker: &Walker<'a>) -> Result<(), Error> {
+        for walker in walker.children()?.into_iter() {
+            self.traverse(&walker)?;
             self.entries.insert(walker.node.id, walker);
         }
+        Ok(())
+    }
+
+    /// Replace the tracer, the default tracer drops all events
+    pub fn set_tracer(&mut self, tracer: Tracer) {
+        self.tracer = tracer;
+    }
+
+    pub fn get_tracer(&self) -> &Tracer {
+        &self.tracer
     }
 
     pub fn find_ids(&self, query: SmartContractQuery) -> Vec<u32> {
@@ -45,7 +64,7 @@ impl<'a> Dictionary<'a> {
             .find(query)
             .map(|ids| {
                 ids.into_iter()
-                   .map(|id| self.entries.get(&id).unwrap())
+                   .filter_map(|id| self.entries.get(&id))
                    .collect::<Vec<&Walker>>()
             })
             .unwrap_or(vec![])
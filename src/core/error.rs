@@ -0,0 +1,58 @@
+use std::fmt;
+use std::error;
+use crate::core::{ Walker, Location };
+use crate::loader::SolidityError;
+
+/// Error of the analysis
+#[derive(Debug)]
+pub enum Error {
+    /// The compiler can not produce an AST
+    Compiler(SolidityError),
+    /// A node is not expected at its position
+    UnsupportedNode { id: u32, name: String, location: Location },
+    /// A node refers to a declaration which is not in the dictionary
+    MissingDeclaration { id: u32, declaration: u32, location: Location },
+    /// A graph is built for a function or a modifier which is not in the dictionary
+    UnknownFunction(u32),
+    /// A node has no id or its `src` attribute (`start:length:file`) is out of the source
+    MalformedSource { id: Option<u32>, location: String },
+}
+
+impl Error {
+    pub fn unsupported_node(walker: &Walker) -> Self {
+        Error::UnsupportedNode {
+            id: walker.node.id,
+            name: walker.node.name.to_string(),
+            location: walker.get_location(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Compiler(error) => write!(f, "{}", error),
+            Error::UnsupportedNode { id, name, location } => {
+                write!(f, "{}: unsupported {} (id: {})", location, name, id)
+            },
+            Error::MissingDeclaration { id, declaration, location } => {
+                write!(f, "{}: missing declaration {} (id: {})", location, declaration, id)
+            },
+            Error::UnknownFunction(id) => write!(f, "unknown function (id: {})", id),
+            Error::MalformedSource { id: Some(id), location } => {
+                write!(f, "malformed source range (id: {}, src: {})", id, location)
+            },
+            Error::MalformedSource { id: None, location } => {
+                write!(f, "malformed source range (src: {})", location)
+            },
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<SolidityError> for Error {
+    fn from(error: SolidityError) -> Self {
+        Error::Compiler(error)
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+/// Position of a node in a source file
+///
+/// Lines and columns are 1-based, columns count characters. The end position is right after the
+/// last character of the node
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    file: String,
+    start: usize,
+    end: usize,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl Location {
+    /// Find the position of byte range `start..end` in `source`
+    pub fn new(file: &str, source: &str, start: usize, end: usize) -> Self {
+        let end = end.min(source.len());
+        let start = start.min(end);
+        let (line, column) = Location::position(source, start);
+        let (end_line, end_column) = Location::position(source, end);
+        Location {
+            file: file.to_string(),
+            start,
+            end,
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+
+    /// Line and column of byte `offset`
+    fn position(source: &str, offset: usize) -> (usize, usize) {
+        let before = source.get(..offset).unwrap_or("");
+        let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+        let line = before.bytes().filter(|byte| *byte == b'\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        (line, column)
+    }
+
+    pub fn get_file(&self) -> &str {
+        &self.file
+    }
+
+    /// Byte range in the source file
+    pub fn get_range(&self) -> (usize, usize) {
+        (self.start, self.end)
+    }
+
+    /// Line and column of the first character
+    pub fn get_start(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    /// Line and column right after the last character
+    pub fn get_end(&self) -> (usize, usize) {
+        (self.end_line, self.end_column)
+    }
+
+    pub fn to_json(&self) -> json::JsonValue {
+        let mut value = json::JsonValue::new_object();
+        value["file"] = self.file.as_str().into();
+        value["start"] = self.start.into();
+        value["end"] = self.end.into();
+        value["line"] = self.line.into();
+        value["column"] = self.column.into();
+        value["endLine"] = self.end_line.into();
+        value["endColumn"] = self.end_column.into();
+        value
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
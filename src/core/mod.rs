@@ -5,9 +5,15 @@ mod walker;
 mod cfg;
 mod dfg;
 mod smart_contract;
+mod error;
+mod tracer;
+mod location;
 
 pub use dict::*;
 pub use walker::*;
 pub use cfg::*;
 pub use dfg::*;
 pub use smart_contract::*;
+pub use error::*;
+pub use tracer::*;
+pub use location::*;
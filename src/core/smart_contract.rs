@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use crate::core::Walker;
+use crate::core::{ Walker, Error };
 
 #[derive(Debug)]
 pub struct ContractProp {
@@ -63,7 +63,7 @@ impl SmartContract {
         }
     }
 
-    pub fn update(&mut self, contract_walkers: Vec<&Walker>) {
+    pub fn update(&mut self, contract_walkers: Vec<&Walker>) -> Result<(), Error> {
         let mut contracts = HashMap::new();
         for contract_walker in contract_walkers {
             let mut prop = ContractProp {
@@ -76,14 +76,16 @@ impl SmartContract {
                 .as_str()
                 .unwrap_or("");
             self.contract_defs.insert(contract_name.to_string(), contract_walker.node.id);
-            for walker in contract_walker.direct_childs(|_| true).into_iter() {
+            for walker in contract_walker.direct_childs(|_| true)?.into_iter() {
                 match walker.node.name {
                     "UsingForDirective" => {
-                        let name = walker.direct_childs(|_| true)
+                        // `using A for *` has no type name
+                        let children = walker.direct_childs(|_| true)?;
+                        let name = children
                             .get(1)
-                            .and_then(|walker| walker.node.attributes["name"].as_str())
-                            .unwrap();
-                        walker.direct_childs(|_| true)
+                            .and_then(|walker| walker.node.attributes.get("name").as_str())
+                            .unwrap_or("*");
+                        children
                             .get(0)
                             .and_then(|walker| walker.node.attributes["referencedDeclaration"].as_u32())
                             .map(|reference| {
@@ -92,7 +94,7 @@ impl SmartContract {
                             });
                     },
                     "InheritanceSpecifier" => {
-                        walker.direct_childs(|_| true)
+                        walker.direct_childs(|_| true)?
                             .get(0)
                             .and_then(|walker| walker.node.attributes["referencedDeclaration"].as_u32())
                             .map(|reference| prop.parents.push(reference));
@@ -140,5 +142,6 @@ impl SmartContract {
             self.contracts.insert(*contract_id, all_functions);
             self.states.insert(*contract_id, all_states);
         }
+        Ok(())
     }
 }
@@ -0,0 +1,103 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::cell::RefCell;
+use crate::core::{
+    Action,
+    DataLink,
+    Variable,
+};
+
+/// An intermediate result of the analysis
+#[derive(Debug, Clone)]
+pub enum TraceEvent {
+    /// Variables decoded from the node `id`, `variables` is empty if decoding fails
+    FlatVariable { id: u32, attributes: Vec<String>, variables: Vec<Variable> },
+    /// Sequence of actions generated at a vertex
+    Actions { vertex_id: u32, actions: Vec<Action> },
+    /// A data dependency link is created
+    Link(DataLink),
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TraceEvent::FlatVariable { id, attributes, variables } => {
+                write!(f, "flat_variable {} {:?}", id, attributes.join("."))?;
+                if variables.is_empty() {
+                    write!(f, "\n\t<undecoded>")?;
+                }
+                for variable in variables {
+                    write!(f, "\n\t{:?}", variable)?;
+                }
+                Ok(())
+            },
+            TraceEvent::Actions { vertex_id, actions } => {
+                write!(f, "actions {}", vertex_id)?;
+                for action in actions {
+                    write!(f, "\n\t{:?}", action)?;
+                }
+                Ok(())
+            },
+            TraceEvent::Link(link) => {
+                let (from_variable, from) = link.get_from();
+                let (to_variable, to) = link.get_to();
+                write!(f, "link {} -> {} ({} -> {})", from, to, from_variable.get_source(), to_variable.get_source())
+            },
+        }
+    }
+}
+
+/// Collect trace events of the analysis
+///
+/// A tracer is disabled by default, events are dropped until it is created with `Tracer::enabled`
+#[derive(Debug, Default)]
+pub struct Tracer {
+    enabled: bool,
+    events: RefCell<Vec<TraceEvent>>,
+}
+
+impl Tracer {
+    /// Create a tracer which drops all events
+    pub fn new() -> Self {
+        Tracer::default()
+    }
+
+    /// Create a tracer which keeps all events
+    pub fn enabled() -> Self {
+        Tracer { enabled: true, events: RefCell::new(vec![]) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Keep an event, events are built lazily so that a disabled tracer costs nothing
+    pub fn record<F>(&self, event: F) where F: FnOnce() -> TraceEvent {
+        if self.enabled {
+            self.events.borrow_mut().push(event());
+        }
+    }
+
+    pub fn get_events(&self) -> Vec<TraceEvent> {
+        self.events.borrow().clone()
+    }
+
+    pub fn clear(&self) {
+        self.events.borrow_mut().clear();
+    }
+
+    /// One event per paragraph in recorded order
+    pub fn format(&self) -> String {
+        self.events.borrow().iter()
+            .map(|event| event.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Write all events to `path`
+    pub fn dump(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.format())
+    }
+}
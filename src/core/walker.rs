@@ -1,14 +1,105 @@
 use std::collections::HashMap;
+use std::ops::Index;
 use json;
+use std::ops::Range;
+use crate::core::{ Error, Location };
+
+/// Layout of an AST
+///
+/// - `Legacy`: nodes keep `name`, `attributes` and `children`, it is the default output of solc < 0.5
+/// - `Compact`: nodes keep `nodeType` and named child fields like `condition`, `trueBody`, `body`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AstFormat {
+    Legacy,
+    Compact,
+}
+
+/// Attributes of an AST node
+///
+/// Attributes are always looked up by legacy keys, keys of compact nodes are translated, e.g
+/// `member_name` reads `memberName` and `type` reads `typeDescriptions.typeString`
+#[derive(Debug, Clone)]
+pub struct Attributes<'a> {
+    value: &'a json::JsonValue,
+    format: AstFormat,
+}
+
+impl<'a> Attributes<'a> {
+    /// Keys with null values, e.g missing components of a ForStatement
+    pub fn null_keys(&self) -> Vec<&'a str> {
+        self.value.entries()
+            .filter(|(_, value)| value.is_null())
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Same as indexing but the value outlives the node
+    pub fn get(&self, key: &str) -> &'a json::JsonValue {
+        match self.format {
+            AstFormat::Legacy => &self.value[key],
+            AstFormat::Compact => self.compact_attribute(key),
+        }
+    }
+
+    fn compact_attribute(&self, key: &str) -> &'a json::JsonValue {
+        let value = self.value;
+        let node_type = value["nodeType"].as_str().unwrap_or("");
+        match (key, node_type) {
+            ("type", _) => &value["typeDescriptions"]["typeString"],
+            ("member_name", _) => &value["memberName"],
+            ("type_conversion", _) => match value["kind"].as_str() {
+                Some("typeConversion") => &json::JsonValue::Boolean(true),
+                _ => &json::JsonValue::Boolean(false),
+            },
+            ("isConstructor", _) => match value["kind"].as_str() {
+                Some("constructor") => &json::JsonValue::Boolean(true),
+                _ => &json::JsonValue::Boolean(false),
+            },
+            ("value", "Identifier") => &value["name"],
+            ("value", "ElementaryTypeNameExpression") => match value["typeName"].is_string() {
+                true => &value["typeName"],
+                false => &value["typeName"]["name"],
+            },
+            ("name", "UserDefinedTypeName") => match value["name"].is_null() {
+                true => &value["pathNode"]["name"],
+                false => &value["name"],
+            },
+            _ => &value[key],
+        }
+    }
+}
+
+impl<'a, 'b> Index<&'b str> for Attributes<'a> {
+    type Output = json::JsonValue;
+
+    fn index(&self, key: &'b str) -> &json::JsonValue {
+        self.get(key)
+    }
+}
 
 /// AST node
 #[derive(Debug, Clone)]
 pub struct Node<'a> {
     pub id: u32,
     pub name: &'a str,
+    /// Name of the source file
+    pub file: &'a str,
     pub source: &'a str,
-    pub attributes: &'a json::JsonValue,
+    pub attributes: Attributes<'a>,
     children: Vec<&'a json::JsonValue>,
+    start: u32,
+}
+
+impl<'a> Node<'a> {
+    pub fn get_format(&self) -> AstFormat {
+        self.attributes.format
+    }
+
+    /// Byte range in the source file
+    pub fn get_range(&self) -> Range<usize> {
+        let start = self.start as usize;
+        start..start + self.source.len()
+    }
 }
 
 /// A pointer to a node of AST tree 
@@ -19,42 +110,136 @@ pub struct Walker<'a> {
 }
 
 impl<'a> Walker<'a> {
-    pub fn new(value: &'a json::JsonValue, source: &'a str) -> Self {
-        let id = value["id"].as_u32().unwrap();
-        let name = value["name"].as_str().unwrap();
-        let src = value["src"].as_str().unwrap();
-        let src = src.split(":")
-            .map(|x| x.parse::<u32>().unwrap())
-            .collect::<Vec<u32>>();
-        let mut children = vec![];
-        for child in value["children"].members() {
-            children.push(child);
-        }
-        let from = src[0] as usize;
-        let to = from + src[1] as usize;
+    /// Create a walker for a node of `file`, `source` is the content of the file
+    pub fn new(value: &'a json::JsonValue, source: &'a str, file: &'a str) -> Result<Self, Error> {
+        let format = match value["nodeType"].is_string() {
+            true => AstFormat::Compact,
+            false => AstFormat::Legacy,
+        };
+        let src = value["src"].as_str().unwrap_or("");
+        let id = value["id"].as_u32()
+            .ok_or_else(|| Error::MalformedSource { id: None, location: src.to_string() })?;
+        let malformed = || Error::MalformedSource { id: Some(id), location: src.to_string() };
+        let range = src.split(":")
+            .take(2)
+            .map(|x| x.parse::<usize>().ok())
+            .collect::<Option<Vec<usize>>>()
+            .filter(|range| range.len() == 2)
+            .ok_or_else(malformed)?;
+        let from = range[0];
+        let to = from + range[1];
+        let name = match format {
+            AstFormat::Legacy => value["name"].as_str(),
+            AstFormat::Compact => value["nodeType"].as_str(),
+        };
+        let name = name.ok_or_else(|| Error::UnsupportedNode {
+            id,
+            name: String::new(),
+            location: Location::new(file, source, from, to),
+        })?;
+        let (attributes, children) = match format {
+            AstFormat::Legacy => {
+                let attributes = Attributes { value: &value["attributes"], format };
+                (attributes, value["children"].members().collect())
+            },
+            AstFormat::Compact => {
+                let attributes = Attributes { value, format };
+                (attributes, Walker::compact_children(value, name))
+            },
+        };
         let node = Node {
             id,
             name,
-            source: &source[from..to],
-            attributes: &value["attributes"],
+            file,
+            source: source.get(from..to).ok_or_else(malformed)?,
+            attributes,
             children,
+            start: from as u32,
         };
-        Walker { node, source }
+        Ok(Walker { node, source })
+    }
+
+    /// Find the line and column of the node, it is computed on demand from the source file
+    pub fn get_location(&self) -> Location {
+        let range = self.node.get_range();
+        Location::new(self.node.file, self.source, range.start, range.end)
+    }
+
+    /// Find all direct childrens, fail if a child is malformed
+    pub fn children(&self) -> Result<Vec<Walker<'a>>, Error> {
+        self.node.children.iter()
+            .map(|child| Walker::new(child, self.source, self.node.file))
+            .collect()
+    }
+
+    /// Collect children of a compact node in the same order as legacy children
+    fn compact_children(value: &'a json::JsonValue, node_type: &str) -> Vec<&'a json::JsonValue> {
+        let fields: &[&str] = match node_type {
+            "SourceUnit" => &["nodes"],
+            "ContractDefinition" => &["baseContracts", "nodes"],
+            "InheritanceSpecifier" => &["baseName", "arguments"],
+            "UsingForDirective" => &["libraryName", "typeName"],
+            "StructDefinition" | "EnumDefinition" => &["members"],
+            "ParameterList" => &["parameters"],
+            "FunctionDefinition" => &["parameters", "returnParameters", "modifiers", "body"],
+            "ModifierDefinition" => &["parameters", "body"],
+            "ModifierInvocation" => &["modifierName", "arguments"],
+            "EventDefinition" | "ErrorDefinition" => &["parameters"],
+            "VariableDeclaration" => &["typeName", "value"],
+            "FunctionTypeName" => &["parameterTypes", "returnParameterTypes"],
+            "Mapping" => &["keyType", "valueType"],
+            "ArrayTypeName" => &["baseType", "length"],
+            "Block" | "UncheckedBlock" => &["statements"],
+            "IfStatement" => &["condition", "trueBody", "falseBody"],
+            "TryStatement" => &["externalCall", "clauses"],
+            "TryCatchClause" => &["parameters", "block"],
+            "WhileStatement" | "DoWhileStatement" => &["condition", "body"],
+            "ForStatement" => &["initializationExpression", "condition", "loopExpression", "body"],
+            "Return" => &["expression"],
+            "EmitStatement" => &["eventCall"],
+            "RevertStatement" => &["errorCall"],
+            "VariableDeclarationStatement" => &["declarations", "initialValue"],
+            "ExpressionStatement" => &["expression"],
+            "Conditional" => &["condition", "trueExpression", "falseExpression"],
+            "Assignment" => &["leftHandSide", "rightHandSide"],
+            "TupleExpression" => &["components"],
+            "UnaryOperation" => &["subExpression"],
+            "BinaryOperation" => &["leftExpression", "rightExpression"],
+            "FunctionCall" => &["expression", "arguments"],
+            "FunctionCallOptions" => &["expression", "options"],
+            "NewExpression" => &["typeName"],
+            "MemberAccess" => &["expression"],
+            "IndexAccess" => &["baseExpression", "indexExpression"],
+            "IndexRangeAccess" => &["baseExpression", "startExpression", "endExpression"],
+            _ => &[],
+        };
+        let mut children = vec![];
+        for field in fields {
+            let child = &value[*field];
+            match child.is_array() {
+                true => children.extend(child.members().filter(|child| child.is_object())),
+                false => if child.is_object() {
+                    children.push(child);
+                },
+            }
+        }
+        children
     }
 
-    /// Find all direct childrens
-    pub fn direct_childs<Filter> (&self, fi: Filter) -> Vec<Walker<'a>> where Filter: Fn(&Walker) -> bool {
+    /// Find all direct childrens accepted by `fi`, fail if a child is malformed
+    pub fn direct_childs<Filter> (&self, fi: Filter) -> Result<Vec<Walker<'a>>, Error> where Filter: Fn(&Walker) -> bool {
         let mut walkers = vec![];
-        for child in self.node.children.iter() {
-            let walker = Walker::new(child, self.source);
+        for walker in self.children()? {
             if fi(&walker) {
                 walkers.push(walker);
             }
         }
-        walkers
+        Ok(walkers)
     }
 
-    pub fn walk<F, I>(&self, bf: bool, ig: I, fi: F) -> Vec<Walker<'a>>
+    /// Find descendants accepted by `fi` without entering those accepted by `ig`, fail if a node
+    /// is malformed
+    pub fn walk<F, I>(&self, bf: bool, ig: I, fi: F) -> Result<Vec<Walker<'a>>, Error>
         where
             F: Fn(&Walker, &Vec<Walker>) -> bool,
             I: Fn(&Walker, &Vec<Walker>) -> bool
@@ -67,9 +252,8 @@ impl<'a> Walker<'a> {
             let item = stacks.pop().unwrap();
             if !ig(&item, &paths[&item.node.id]) {
                 if !(fi(&item, &paths[&item.node.id]) && bf) {
-                    for child in item.node.children.iter() {
+                    for walker in item.direct_childs(|_| true)? {
                         let mut path = paths[&item.node.id].clone();
-                        let walker = Walker::new(child, item.source);
                         path.push(walker.clone());
                         paths.insert(walker.node.id, path);
                         stacks.push(walker);
@@ -80,6 +264,6 @@ impl<'a> Walker<'a> {
                 walkers.insert(0, item);
             }
         }
-        walkers
+        Ok(walkers)
     }
 }
@@ -8,6 +8,8 @@ use crate::core::{
     Variable,
     Assignment,
     Declaration,
+    TraceEvent,
+    Error,
 };
 
 /// Data flow graph
@@ -89,7 +91,7 @@ impl<'a> DataFlowGraph<'a> {
     /// All elements in that pattern will be removed from the sequence.
     ///
     /// The loop will stop if no sequence changes happen
-    pub fn find_links(&mut self) -> HashSet<DataLink> {
+    pub fn find_links(&mut self) -> Result<HashSet<DataLink>, Error> {
         let dict = self.cfg.get_dict();
         let stop = self.cfg.get_stop();
         let mut stack: Vec<(u32, u32, Vec<Action>)> = vec![];
@@ -108,13 +110,13 @@ impl<'a> DataFlowGraph<'a> {
             let mut new_actions = vec![];
             let mut assignments = vec![];
             let mut variables = HashSet::new();
-            dict.walker_at(id).map(|walker| {
-                variables.extend(Variable::parse(walker, dict));
-                assignments.extend(Assignment::parse(walker, dict));
-                for declaration in Declaration::parse(walker, dict) {
+            if let Some(walker) = dict.walker_at(id) {
+                variables.extend(Variable::parse(walker, dict)?);
+                assignments.extend(Assignment::parse(walker, dict)?);
+                for declaration in Declaration::parse(walker, dict)? {
                     assignments.push(declaration.get_assignment().clone());
                 }
-            });
+            }
             for assignment in assignments {
                 for l in assignment.get_lhs().clone() {
                     match assignment.get_op() {
@@ -131,11 +133,10 @@ impl<'a> DataFlowGraph<'a> {
                     new_actions.push(Action::Use(r, id));
                 }
             }
-            println!("-----{}------", id);
             for var in variables {
-                println!("\t {:?}", var);
                 new_actions.push(Action::Use(var, id));
             }
+            dict.get_tracer().record(|| TraceEvent::Actions { vertex_id: id, actions: new_actions.clone() });
             self.new_actions.insert(id, new_actions.clone());
             actions.extend(new_actions.clone());
             cur_table.extend(pre_table);
@@ -162,6 +163,7 @@ impl<'a> DataFlowGraph<'a> {
                                                     (variable.clone(), *id),
                                                     (kill_var.clone(), kill_id),
                                                 );
+                                                dict.get_tracer().record(|| TraceEvent::Link(data_link.clone()));
                                                 links.insert(data_link);
                                                 cur_table.remove(action);
                                                 false
@@ -173,6 +175,7 @@ impl<'a> DataFlowGraph<'a> {
                                                         (variable.clone(), *id),
                                                         (kill_var.clone(), kill_id),
                                                     );
+                                                    dict.get_tracer().record(|| TraceEvent::Link(data_link.clone()));
                                                     links.insert(data_link);
                                                     false
                                                 } else {
@@ -209,6 +212,6 @@ impl<'a> DataFlowGraph<'a> {
                 }
             }
         }
-        links
+        Ok(links)
     }
 }
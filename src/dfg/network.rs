@@ -7,6 +7,8 @@ use crate::core::{
     SmartContractQuery,
     Action,
     Variable,
+    Error,
+    TraceEvent,
 };
 
 use std::collections::{
@@ -30,7 +32,7 @@ pub struct Network<'a> {
 }
 
 impl<'a> Network<'a> {
-    pub fn new(dict: &'a Dictionary, contract_id: u32) -> Self {
+    pub fn new(dict: &'a Dictionary, contract_id: u32) -> Result<Self, Error> {
         let mut network = Network {
             dict,
             links: HashSet::new(),
@@ -39,8 +41,8 @@ impl<'a> Network<'a> {
             context: HashMap::new(),
             contract_id,
         };
-        network.find_links();
-        network
+        network.find_links()?;
+        Ok(network)
     }
 
     pub fn get_links(&self) -> &HashSet<DataLink> {
@@ -135,7 +137,7 @@ impl<'a> Network<'a> {
         index_links
     }
 
-    fn find_fcall_links(&mut self) -> HashSet<DataLink>  {
+    fn find_fcall_links(&mut self) -> Result<HashSet<DataLink>, Error> {
         let mut context = HashMap::new();
         let mut fcall_links = HashSet::new();
         let mut all_actions = HashMap::new();
@@ -167,12 +169,17 @@ impl<'a> Network<'a> {
         };
         for (fcall_id, invoked_parameters) in all_fcalls {
             let fcall_variables = get_variables(fcall_id);
-            self.dict.walker_at(fcall_id).map(|walker| {
-                let walkers = walker.direct_childs(|_| true);
-                let declaration = walkers[0].node.attributes["referencedDeclaration"].as_u32();
-                let is_user_defined = declaration.and_then(|declaration| all_returns.get(&declaration)).is_some();
-                match is_user_defined {
-                    false => {
+            if let Some(walker) = self.dict.walker_at(fcall_id) {
+                let walkers = walker.direct_childs(|_| true)?;
+                let declaration = walkers.first()
+                    .and_then(|walker| walker.node.attributes["referencedDeclaration"].as_u32());
+                let user_defined = declaration.and_then(|declaration| {
+                    let returns = all_returns.get(&declaration)?;
+                    let defined_parameters = all_defined_parameters.get(&declaration)?;
+                    Some((returns, defined_parameters))
+                });
+                match user_defined {
+                    None => {
                         for param_id in (&invoked_parameters[2..]).iter() {
                             let param_variables = get_variables(*param_id);
                             let from = (fcall_variables.clone(), fcall_id);
@@ -194,10 +201,7 @@ impl<'a> Network<'a> {
                             }
                         });
                     },
-                    true => {
-                        let declaration = declaration.unwrap();
-                        let returns = all_returns.get(&declaration).unwrap();
-                        let defined_parameters = all_defined_parameters.get(&declaration).unwrap();
+                    Some((returns, defined_parameters)) => {
                         for return_id in returns {
                             let return_variables = get_variables(*return_id);
                             let from = (fcall_variables.clone(), fcall_id);
@@ -212,7 +216,8 @@ impl<'a> Network<'a> {
                         }
                         let defined_len = defined_parameters.len();
                         let invoked_len = invoked_parameters.len();
-                        for idx in 0..invoked_len - 2 {
+                        // Parameters are matched from the end, extra arguments have no definition
+                        for idx in 0..invoked_len.saturating_sub(2).min(defined_len) {
                             let defined_parameter_variables = get_variables(defined_parameters[defined_len - idx - 1]);
                             let invoked_parameter_variables = get_variables(invoked_parameters[invoked_len - idx - 1]);
                             let from = (defined_parameter_variables, defined_parameters[defined_len - idx - 1]);
@@ -234,37 +239,41 @@ impl<'a> Network<'a> {
                         });
                     }
                 }
-            });
+            }
         }
         self.context = context;
-        fcall_links
+        Ok(fcall_links)
     }
 
-    fn find_external_links(&mut self) -> HashSet<DataLink> {
+    fn find_external_links(&mut self) -> Result<HashSet<DataLink>, Error> {
         let mut external_links = HashSet::new();
         external_links.extend(self.find_assignment_links());
         external_links.extend(self.find_index_links());
-        external_links.extend(self.find_fcall_links());
-        external_links
+        external_links.extend(self.find_fcall_links()?);
+        Ok(external_links)
     }
 
-    fn find_internal_links(&mut self) -> HashSet<DataLink> {
+    fn find_internal_links(&mut self) -> Result<HashSet<DataLink>, Error> {
         let mut links = HashSet::new();
         let function_ids = self.dict.find_ids(SmartContractQuery::FunctionsByContractId(self.contract_id));
         for function_id in function_ids {
-            let cfg = ControlFlowGraph::new(self.dict, self.contract_id, function_id);
+            let cfg = ControlFlowGraph::new(self.dict, self.contract_id, function_id)?;
             let mut dfg = DataFlowGraph::new(cfg);
-            links.extend(dfg.find_links());
+            links.extend(dfg.find_links()?);
             self.dfgs.insert(function_id, dfg);
         }
-        links
+        Ok(links)
     }
 
-    fn find_links(&mut self) {
-        let internal_links = self.find_internal_links();
-        let external_links = self.find_external_links();
+    fn find_links(&mut self) -> Result<(), Error> {
+        let internal_links = self.find_internal_links()?;
+        let external_links = self.find_external_links()?;
+        for link in external_links.iter() {
+            self.dict.get_tracer().record(|| TraceEvent::Link(link.clone()));
+        }
         self.links.extend(internal_links);
         self.links.extend(external_links);
+        Ok(())
     }
 
     fn network_traverse(
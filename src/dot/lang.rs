@@ -29,6 +29,12 @@ impl Dot {
         for vertex in cfg.get_vertices().iter() {
             let id = vertex.get_id();
             let source = vertex.get_source().replace("\"", "");
+            let location = vertex.get_location();
+            // Start and stop points have no location
+            let source = match location.get_start() {
+                (0, _) => source,
+                (line, column) => format!("{}:{}: {}", line, column, source),
+            };
             let shape = match vertex.get_shape() {
                 Shape::Point => "point",
                 Shape::Box => "box",
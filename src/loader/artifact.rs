@@ -0,0 +1,54 @@
+use std::fs;
+use std::path::Path;
+use std::collections::HashMap;
+use crate::loader::{
+    SolidityError,
+    SolidityASTOutput,
+    CompilerMessage,
+    MessageSeverity,
+};
+
+/// Output of a previous compilation
+///
+/// It reads the output of `--combined-json ast`, the output of `--standard-json` and build info
+/// files of Hardhat or Foundry which keep both standard json input and output. Source codes are
+/// taken from the standard json input if possible, otherwise they are read from `source_root`.
+pub struct Artifact<'a> {
+    path: &'a Path,
+    source_root: &'a Path,
+}
+
+impl<'a> Artifact<'a> {
+    pub fn new(path: &'a Path, source_root: &'a Path) -> Self {
+        Artifact { path, source_root }
+    }
+
+    /// Read the AST without running a compiler
+    pub fn load(&self) -> Result<SolidityASTOutput, SolidityError> {
+        let content = fs::read_to_string(self.path)?;
+        let mut value = json::parse(&content)
+            .map_err(|error| SolidityError::InvalidArtifact(error.to_string()))?;
+        let input = value["input"].take();
+        let output = match value["output"].is_object() {
+            true => value["output"].take(),
+            false => value,
+        };
+        if !output["sources"].is_object() {
+            let reason = format!("{} has no sources", self.path.display());
+            return Err(SolidityError::InvalidArtifact(reason));
+        }
+        let messages = CompilerMessage::from_output(&output);
+        if messages.iter().any(|message| message.severity == MessageSeverity::Error) {
+            return Err(SolidityError::Compiler(messages));
+        }
+        let mut sources = HashMap::new();
+        for (path, _) in output["sources"].entries() {
+            let source = match input["sources"][path]["content"].as_str() {
+                Some(source) => source.to_string(),
+                None => fs::read_to_string(self.source_root.join(path))?,
+            };
+            sources.insert(path.to_string(), source);
+        }
+        Ok(SolidityASTOutput { ast: output.dump(), sources })
+    }
+}
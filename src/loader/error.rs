@@ -0,0 +1,60 @@
+use std::fmt;
+use std::io;
+use std::error;
+use crate::loader::CompilerMessage;
+
+/// Error of the loader
+#[derive(Debug)]
+pub enum SolidityError {
+    /// Reading a contract or running the compiler failed
+    Io(io::Error),
+    /// A provider failed to fetch the compiler
+    Download(String),
+    /// None of the providers has a compiler for the version
+    CompilerNotFound(String),
+    /// A pragma has an invalid version constraint
+    InvalidPragma(String),
+    /// No compiler release satisfies all pragmas
+    UnsatisfiableVersion(String),
+    /// The compiler rejects the contract
+    Compiler(Vec<CompilerMessage>),
+    /// A compiler output file can not be read
+    InvalidArtifact(String),
+}
+
+impl fmt::Display for SolidityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolidityError::Io(error) => write!(f, "{}", error),
+            SolidityError::Download(reason) => write!(f, "failed to download compiler: {}", reason),
+            SolidityError::CompilerNotFound(version) => write!(f, "no compiler found for {}", version),
+            SolidityError::InvalidPragma(constraint) => write!(f, "invalid pragma solidity {}", constraint),
+            SolidityError::UnsatisfiableVersion(constraints) => write!(f, "no compiler satisfies {}", constraints),
+            SolidityError::InvalidArtifact(reason) => write!(f, "invalid artifact: {}", reason),
+            SolidityError::Compiler(messages) => {
+                let messages = messages.iter()
+                    .map(|message| message.formatted_message.as_str())
+                    .collect::<Vec<&str>>();
+                write!(f, "compilation failed\n{}", messages.join("\n"))
+            },
+        }
+    }
+}
+
+impl error::Error for SolidityError {}
+
+impl From<io::Error> for SolidityError {
+    fn from(error: io::Error) -> Self {
+        SolidityError::Io(error)
+    }
+}
+
+impl From<SolidityError> for io::Error {
+    fn from(error: SolidityError) -> Self {
+        match error {
+            SolidityError::Io(error) => error,
+            SolidityError::CompilerNotFound(_) => io::Error::new(io::ErrorKind::NotFound, error.to_string()),
+            _ => io::Error::other(error.to_string()),
+        }
+    }
+}
@@ -2,6 +2,14 @@
 //!
 mod solidity; 
 mod option;
+mod error;
+mod provider;
+mod version;
+mod artifact;
 
 pub use solidity::*;
 pub use option::*;
+pub use error::*;
+pub use provider::*;
+pub use version::*;
+pub use artifact::*;
@@ -1,8 +1,14 @@
-use std::path::Path;
+use std::path::{ Path, PathBuf };
 use std::collections::HashMap;
 
 pub enum SolidityOutputKind {
+    /// Run the compiler with `--combined-json ast`
     AST,
+    /// Run the compiler with `--combined-json ast,compact-format`, the output is an `AST` output
+    /// whose AST is in the compact format
+    CompactAST,
+    /// Run the compiler with `--standard-json`
+    StandardJson,
 }
 
 pub struct SolidityASTOutput {
@@ -10,12 +16,102 @@ pub struct SolidityASTOutput {
     pub sources: HashMap<String, String>,
 }
 
+/// Severity of a compiler message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// Error or warning reported by the compiler
+#[derive(Debug, Clone)]
+pub struct CompilerMessage {
+    pub severity: MessageSeverity,
+    /// Type of the message, e.g `TypeError`, `ParserError`
+    pub kind: String,
+    pub message: String,
+    pub formatted_message: String,
+    /// File name, start and end offset of the message
+    pub location: Option<(String, usize, usize)>,
+}
+
+impl CompilerMessage {
+    /// Read errors and warnings in the output of `--standard-json` mode
+    pub fn from_output(output: &json::JsonValue) -> Vec<CompilerMessage> {
+        let mut messages = vec![];
+        for error in output["errors"].members() {
+            let severity = match error["severity"].as_str() {
+                Some("error") => MessageSeverity::Error,
+                Some("warning") => MessageSeverity::Warning,
+                _ => MessageSeverity::Info,
+            };
+            let location = &error["sourceLocation"];
+            let location = match (location["file"].as_str(), location["start"].as_usize(), location["end"].as_usize()) {
+                (Some(file), Some(start), Some(end)) => Some((file.to_string(), start, end)),
+                _ => None,
+            };
+            messages.push(CompilerMessage {
+                severity,
+                kind: error["type"].as_str().unwrap_or("").to_string(),
+                message: error["message"].as_str().unwrap_or("").to_string(),
+                formatted_message: error["formattedMessage"].as_str().unwrap_or("").to_string(),
+                location,
+            });
+        }
+        messages
+    }
+
+    /// Wrap the error output of the compiler
+    pub fn from_stderr(stderr: &[u8]) -> Self {
+        let message = String::from_utf8_lossy(stderr).trim().to_string();
+        CompilerMessage {
+            severity: MessageSeverity::Error,
+            kind: String::from("CompilerError"),
+            formatted_message: message.clone(),
+            message,
+            location: None,
+        }
+    }
+}
+
+pub struct SolidityStandardJsonOutput {
+    /// Output of the compiler, the AST of each file is kept in `sources`
+    pub ast: String,
+    pub sources: HashMap<String, String>,
+    /// Warnings and infos of the compiler
+    pub messages: Vec<CompilerMessage>,
+}
+
 pub enum SolidityOutput {
     AST(SolidityASTOutput),
+    StandardJson(SolidityStandardJsonOutput),
 }
 
 pub struct SolidityOption<'a> {
     pub bin_dir: &'a Path,
     pub contract: &'a Path,
     pub kind: SolidityOutputKind,
+    /// Import remappings, e.g `@openzeppelin/=node_modules/@openzeppelin/`
+    pub remappings: Vec<String>,
+    /// Root of non-relative imports
+    pub base_path: Option<PathBuf>,
+    /// Other roots of non-relative imports, e.g `node_modules/`, `lib/`
+    pub include_paths: Vec<PathBuf>,
+    /// Number of optimizer runs, the optimizer is disabled if it is `None`
+    pub optimizer: Option<u32>,
+}
+
+impl<'a> SolidityOption<'a> {
+    pub fn new(bin_dir: &'a Path, contract: &'a Path, kind: SolidityOutputKind) -> Self {
+        SolidityOption {
+            bin_dir,
+            contract,
+            kind,
+            remappings: vec![],
+            base_path: None,
+            include_paths: vec![],
+            optimizer: None,
+        }
+    }
 }
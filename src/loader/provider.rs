@@ -0,0 +1,235 @@
+use std::fs;
+use std::io::copy;
+use std::path::{ Path, PathBuf };
+use std::process::Command;
+use std::cell::OnceCell;
+use std::time::Duration;
+use std::collections::HashMap;
+use std::os::unix::fs::OpenOptionsExt;
+use regex::Regex;
+use crate::loader::{ SolidityError, Version };
+
+/// Source of solc binaries
+///
+/// `Solidity` consults its providers in order and runs the first compiler found. The version is
+/// formatted as a release tag, e.g `v0.4.24`. A provider returns `Ok(None)` if it does not have
+/// the version so that the next provider is consulted.
+pub trait CompilerProvider {
+    fn provide(&self, version: &str) -> Result<Option<PathBuf>, SolidityError>;
+
+    /// Versions the provider can provide, the newest one satisfying the pragmas is compiled with
+    fn versions(&self) -> Result<Vec<Version>, SolidityError> {
+        Ok(vec![])
+    }
+}
+
+/// Ask a compiler for its version
+fn compiler_version(compiler_path: &Path) -> Option<Version> {
+    let version_regex = Regex::new(r"Version: (\d+\.\d+\.\d+)").unwrap();
+    let output = Command::new(compiler_path).arg("--version").output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let cap = version_regex.captures(&stdout)?;
+    Version::parse(&cap[1])
+}
+
+/// Compilers stored as `<dir>/<version>`, e.g `bin/v0.4.24`
+pub struct LocalProvider {
+    dir: PathBuf,
+}
+
+impl LocalProvider {
+    pub fn new(dir: &Path) -> Self {
+        LocalProvider { dir: dir.to_path_buf() }
+    }
+}
+
+impl CompilerProvider for LocalProvider {
+    fn provide(&self, version: &str) -> Result<Option<PathBuf>, SolidityError> {
+        let compiler_path = self.dir.join(version);
+        match compiler_path.is_file() {
+            true => Ok(Some(compiler_path)),
+            false => Ok(None),
+        }
+    }
+
+    fn versions(&self) -> Result<Vec<Version>, SolidityError> {
+        let mut versions = vec![];
+        if let Ok(entries) = fs::read_dir(&self.dir) {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let name = entry.file_name();
+                let version = name.to_str()
+                    .filter(|name| name.starts_with('v'))
+                    .and_then(Version::parse);
+                if let Some(version) = version {
+                    versions.push(version);
+                }
+            }
+        }
+        Ok(versions)
+    }
+}
+
+/// A compiler at an explicit path
+///
+/// It only provides the version it reports with `--version` so that pragmas which it does not
+/// satisfy fall through to the next provider. Its version is asked once
+pub struct PathProvider {
+    path: PathBuf,
+    version: OnceCell<Option<Version>>,
+}
+
+impl PathProvider {
+    pub fn new(path: &Path) -> Self {
+        PathProvider { path: path.to_path_buf(), version: OnceCell::new() }
+    }
+
+    fn version(&self) -> Option<Version> {
+        *self.version.get_or_init(|| compiler_version(&self.path))
+    }
+}
+
+impl CompilerProvider for PathProvider {
+    fn provide(&self, version: &str) -> Result<Option<PathBuf>, SolidityError> {
+        let provided = self.version().iter().any(|other| Version::parse(version) == Some(*other));
+        match provided {
+            true => Ok(Some(self.path.clone())),
+            false => Ok(None),
+        }
+    }
+
+    fn versions(&self) -> Result<Vec<Version>, SolidityError> {
+        Ok(self.version().iter().cloned().collect())
+    }
+}
+
+/// The `solc` found on `$PATH`, it only provides the version it reports
+#[derive(Default)]
+pub struct SystemProvider {
+    version: OnceCell<Option<Version>>,
+}
+
+impl SystemProvider {
+    pub fn new() -> Self {
+        SystemProvider { version: OnceCell::new() }
+    }
+
+    fn version(&self) -> Option<Version> {
+        *self.version.get_or_init(|| compiler_version(Path::new("solc")))
+    }
+}
+
+impl CompilerProvider for SystemProvider {
+    fn provide(&self, version: &str) -> Result<Option<PathBuf>, SolidityError> {
+        let provided = self.version().iter().any(|other| Version::parse(version) == Some(*other));
+        match provided {
+            true => Ok(Some(PathBuf::from("solc"))),
+            false => Ok(None),
+        }
+    }
+
+    fn versions(&self) -> Result<Vec<Version>, SolidityError> {
+        Ok(self.version().iter().cloned().collect())
+    }
+}
+
+/// Directory of static linux builds on solc-bin
+const SOLC_BIN_URL: &str = "https://binaries.soliditylang.org/linux-amd64";
+
+/// Age after which the cached list of releases is downloaded again
+const LIST_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Fetch `url`, fail unless the server answers with a success status
+fn download(url: &str) -> Result<reqwest::Response, SolidityError> {
+    let response = reqwest::get(url)
+        .map_err(|error| SolidityError::Download(error.to_string()))?;
+    if !response.status().is_success() {
+        let reason = format!("{} returns {}", url, response.status());
+        return Err(SolidityError::Download(reason));
+    }
+    Ok(response)
+}
+
+/// Fetch the solc-bin list of builds
+fn download_list() -> Result<String, SolidityError> {
+    download(&format!("{}/list.json", SOLC_BIN_URL))?
+        .text()
+        .map_err(|error| SolidityError::Download(error.to_string()))
+}
+
+/// Download releases listed in solc-bin `list.json` to `<dir>/<version>`
+///
+/// The list is kept in `<dir>/list.json` and downloaded again once a day, the stale list is used
+/// when solc-bin can not be reached. Releases older than 0.4.10 have no linux build so they are
+/// not provided
+pub struct DownloadProvider {
+    dir: PathBuf,
+    releases: OnceCell<HashMap<Version, String>>,
+}
+
+impl DownloadProvider {
+    pub fn new(dir: &Path) -> Self {
+        DownloadProvider { dir: dir.to_path_buf(), releases: OnceCell::new() }
+    }
+
+    /// File names of releases on solc-bin by version
+    fn releases(&self) -> Result<&HashMap<Version, String>, SolidityError> {
+        if let Some(releases) = self.releases.get() {
+            return Ok(releases);
+        }
+        let list_path = self.dir.join("list.json");
+        let fresh = fs::metadata(&list_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .map(|age| age < LIST_MAX_AGE)
+            .unwrap_or(false);
+        let content = match fresh {
+            true => fs::read_to_string(&list_path)?,
+            false => match download_list() {
+                Ok(content) => {
+                    fs::write(&list_path, &content).ok();
+                    content
+                },
+                Err(error) => fs::read_to_string(&list_path).map_err(|_| error)?,
+            },
+        };
+        let list = json::parse(&content)
+            .map_err(|error| SolidityError::Download(format!("invalid list.json: {}", error)))?;
+        let releases = list["releases"].entries()
+            .filter_map(|(version, file_name)| {
+                Some((Version::parse(version)?, file_name.as_str()?.to_string()))
+            })
+            .collect();
+        Ok(self.releases.get_or_init(|| releases))
+    }
+}
+
+impl CompilerProvider for DownloadProvider {
+    fn provide(&self, version: &str) -> Result<Option<PathBuf>, SolidityError> {
+        let compiler_path = self.dir.join(version);
+        if compiler_path.is_file() {
+            return Ok(Some(compiler_path));
+        }
+        let releases = self.releases()?;
+        let file_name = match Version::parse(version).and_then(|version| releases.get(&version)) {
+            Some(file_name) => file_name,
+            None => return Ok(None),
+        };
+        let mut response = download(&format!("{}/{}", SOLC_BIN_URL, file_name))?;
+        let mut dest = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .mode(0o777)
+            .open(&compiler_path)?;
+        if let Err(error) = copy(&mut response, &mut dest) {
+            fs::remove_file(&compiler_path).ok();
+            return Err(SolidityError::Io(error));
+        }
+        Ok(Some(compiler_path))
+    }
+
+    fn versions(&self) -> Result<Vec<Version>, SolidityError> {
+        Ok(self.releases()?.keys().cloned().collect())
+    }
+}
@@ -1,85 +1,313 @@
 use std::io::*;
 use std::fs;
-use std::process::{ Command, Output };
+use std::path::{ Path, PathBuf };
+use std::ffi::OsString;
+use std::collections::HashSet;
+use regex::Regex;
+use std::process::{ Command, Output, Stdio };
 use std::collections::HashMap;
-use std::os::unix::fs::OpenOptionsExt;
+use crate::loader::{
+    SolidityError,
+    Version,
+    VersionReq,
+    CompilerProvider,
+    LocalProvider,
+    DownloadProvider,
+};
 use crate::loader::option::{
     SolidityOption,
     SolidityOutput,
     SolidityOutputKind,
     SolidityASTOutput,
+    SolidityStandardJsonOutput,
+    CompilerMessage,
+    MessageSeverity,
 };
 
+/// Compiler used when contracts have no pragma
+const DEFAULT_VERSION: &str = "v0.4.24";
+
 pub struct Solidity<'a> {
     option: SolidityOption<'a>,
+    providers: Vec<Box<dyn CompilerProvider>>,
 }
 
 impl<'a> Solidity<'a> {
+    /// Look for compilers in `bin_dir` and download missing ones to `bin_dir`
     pub fn new(option: SolidityOption<'a>) -> Self {
-        Solidity { option }
+        let providers: Vec<Box<dyn CompilerProvider>> = vec![
+            Box::new(LocalProvider::new(option.bin_dir)),
+            Box::new(DownloadProvider::new(option.bin_dir)),
+        ];
+        Solidity { option, providers }
     }
 
-    fn extract_source_map(&self, ast: &String) -> Result<HashMap<String, String>> {
-        let mut ret = HashMap::new();
-        let source_list = ast.split("\"sourceList\":[").collect::<Vec<&str>>();
-        let source_list = source_list[1];
-        if let Some(close_bracket) = source_list.find("]") {
-            let source_list = &source_list[0..close_bracket];
-            let source_list = source_list.split("\"").collect::<Vec<&str>>();
-            for i in 0..source_list.len() {
-                if i % 2 == 1 {
-                    let path = source_list[i].to_string();
-                    let source = fs::read_to_string(&path)?;
-                    ret.insert(path, source);
+    /// Look for compilers in `providers` only, the first provider has the highest priority
+    pub fn with_providers(option: SolidityOption<'a>, providers: Vec<Box<dyn CompilerProvider>>) -> Self {
+        Solidity { option, providers }
+    }
+
+    /// Apply the longest matching remapping, e.g `@openzeppelin/=node_modules/@openzeppelin/`
+    ///
+    /// A remapping with a context (`context:prefix=target`) only applies to imports of files
+    /// whose path starts with the context, the longest context wins before the longest prefix
+    fn remap(&self, importer: &str, path: &str) -> Option<String> {
+        let mut remapped: Option<((usize, usize), String)> = None;
+        for remapping in self.option.remappings.iter() {
+            if let Some((prefix, target)) = remapping.split_once('=') {
+                let (context, prefix) = prefix.split_once(':').unwrap_or(("", prefix));
+                let rank = (context.len(), prefix.len());
+                let longer = remapped.as_ref().map(|(other, _)| rank > *other).unwrap_or(true);
+                if !prefix.is_empty() && importer.starts_with(context) && path.starts_with(prefix) && longer {
+                    remapped = Some((rank, format!("{}{}", target, &path[prefix.len()..])));
+                }
+            }
+        }
+        remapped.map(|(_, path)| path)
+    }
+
+    /// Find a source file on disk, the path is tried as it is, then relative to the base path and
+    /// include paths, before and after remapping
+    fn resolve_source(&self, importer: &str, path: &str) -> Option<PathBuf> {
+        let mut roots = vec![PathBuf::new()];
+        roots.extend(self.option.base_path.iter().cloned());
+        roots.extend(self.option.include_paths.iter().cloned());
+        let mut paths = vec![path.to_string()];
+        paths.extend(self.remap(importer, path));
+        for path in paths.iter() {
+            for root in roots.iter() {
+                let candidate = root.join(path);
+                if candidate.is_file() {
+                    return Some(candidate);
                 }
             }
         }
+        None
+    }
+
+    /// Read all sources listed in the output of the compiler
+    fn read_sources(&self, paths: Vec<&str>) -> Result<HashMap<String, String>> {
+        let mut ret = HashMap::new();
+        for path in paths {
+            // Paths in the output are remapped already
+            let resolved_path = self.resolve_source("", path).unwrap_or(PathBuf::from(path));
+            let source = fs::read_to_string(&resolved_path)?;
+            ret.insert(path.to_string(), source);
+        }
         Ok(ret)
     }
 
-    fn extract_version(&self) -> Result<String> {
-        let mut version = String::from("v0.4.24");
-        let source = fs::read_to_string(self.option.contract)?;
-        let source = source.trim();
-        if source.starts_with("pragma solidity ^") {
-            let temp = source.split("^").collect::<Vec<&str>>();
-            let temp = temp[1].split(";").collect::<Vec<&str>>();
-            version = format!("v{}", temp[0]);
+    /// Arguments for remappings and import paths
+    ///
+    /// `--base-path` is supported since 0.6.9 and `--include-path` since 0.8.8, older compilers
+    /// only resolve imports with remappings
+    fn import_args(&self, version: &str) -> Vec<OsString> {
+        let version = Version::parse(version).unwrap_or(Version::new(0, 0, 0));
+        let mut args = vec![];
+        let mut allowed_paths = vec![];
+        allowed_paths.extend(self.option.contract.parent().map(|dir| dir.to_path_buf()));
+        if let Some(base_path) = self.option.base_path.as_ref() {
+            allowed_paths.push(base_path.clone());
+            if version >= Version::new(0, 6, 9) {
+                args.push(OsString::from("--base-path"));
+                args.push(base_path.clone().into_os_string());
+            }
+        }
+        for include_path in self.option.include_paths.iter() {
+            allowed_paths.push(include_path.clone());
+            if version >= Version::new(0, 8, 8) {
+                args.push(OsString::from("--include-path"));
+                args.push(include_path.clone().into_os_string());
+            }
+        }
+        for remapping in self.option.remappings.iter() {
+            allowed_paths.extend(remapping.split_once('=').map(|(_, target)| PathBuf::from(target)));
         }
-        Ok(version)
+        let allowed_paths = allowed_paths.iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .filter(|path| !path.is_empty())
+            .collect::<Vec<String>>();
+        if !allowed_paths.is_empty() {
+            args.push(OsString::from("--allow-paths"));
+            args.push(OsString::from(allowed_paths.join(",")));
+        }
+        args
     }
 
-    pub fn compile(&self) -> Result<SolidityOutput> {
-        let version = self.extract_version()?;
-        let solc_url = "https://github.com/ethereum/solidity/releases/download";
-        let solc_url = format!("{}/{}/solc-static-linux", solc_url, version);
-        let compiler_path = self.option.bin_dir.join(&version);
-        if !compiler_path.exists() {
-            let mut response = reqwest::get(&solc_url).unwrap();
-            let mut dest = fs::OpenOptions::new()
-                .create(true)
-                .write(true)
-                .mode(0o777)
-                .open(compiler_path)?;
-            copy(&mut response, &mut dest)?;
-        }
-        let compiler_path = self.option.bin_dir.join(&version);
+    /// Remove comments so that commented pragmas and imports are ignored
+    fn strip_comments(source: &str) -> String {
+        let comment_regex = Regex::new(r"(?s)/\*.*?\*/|//[^\n]*").unwrap();
+        comment_regex.replace_all(source, "").to_string()
+    }
+
+    /// Find the path of an imported file
+    fn resolve_import(&self, from: &Path, import: &str) -> PathBuf {
+        match import.starts_with("./") || import.starts_with("../") {
+            true => from.parent().map(|dir| dir.join(import)).unwrap_or(PathBuf::from(import)),
+            false => self.resolve_source(&from.to_string_lossy(), import).unwrap_or(PathBuf::from(import)),
+        }
+    }
+
+    /// Collect pragma constraints of the contract and all files it imports
+    fn extract_constraints(&self) -> std::result::Result<Vec<String>, SolidityError> {
+        let pragma_regex = Regex::new(r"pragma\s+solidity\s+([^;]+);").unwrap();
+        let import_regex = Regex::new(r#"import\s+(?:[^;]*?\s+from\s+)?["']([^"']+)["']"#).unwrap();
+        let mut constraints = vec![];
+        let mut visited = HashSet::new();
+        let mut stacks = vec![self.option.contract.to_path_buf()];
+        while let Some(path) = stacks.pop() {
+            if !visited.insert(path.clone()) { continue; }
+            // Missing imports are reported by the compiler
+            let source = match fs::read_to_string(&path) {
+                Ok(source) => Solidity::strip_comments(&source),
+                Err(error) => match path.as_path() == self.option.contract {
+                    true => return Err(SolidityError::Io(error)),
+                    false => continue,
+                },
+            };
+            for cap in pragma_regex.captures_iter(&source) {
+                constraints.push(cap[1].trim().to_string());
+            }
+            for cap in import_regex.captures_iter(&source) {
+                stacks.push(self.resolve_import(&path, &cap[1]));
+            }
+        }
+        Ok(constraints)
+    }
+
+    /// Pick the newest version satisfying all pragmas in the import graph
+    ///
+    /// Versions of all providers are considered, a provider which can not list its versions only
+    /// fails the resolution if no other provider has a matching version. The default version is
+    /// used if no pragma is found
+    fn extract_version(&self) -> std::result::Result<String, SolidityError> {
+        let constraints = self.extract_constraints()?;
+        let mut reqs = vec![];
+        for constraint in constraints.iter() {
+            match VersionReq::parse(constraint) {
+                Some(req) => reqs.push(req),
+                None => return Err(SolidityError::InvalidPragma(constraint.to_string())),
+            }
+        }
+        if reqs.is_empty() {
+            return Ok(DEFAULT_VERSION.to_string());
+        }
+        let matches = |version: &Version| reqs.iter().all(|req| req.matches(version));
+        let mut newest = None;
+        let mut failure = None;
+        for provider in self.providers.iter() {
+            match provider.versions() {
+                Ok(versions) => {
+                    newest = versions.into_iter().filter(|version| matches(version)).chain(newest).max();
+                },
+                Err(error) => {
+                    failure = failure.or(Some(error));
+                },
+            }
+        }
+        match (newest, failure) {
+            (Some(version), _) => Ok(version.tag()),
+            (None, Some(error)) => Err(error),
+            (None, None) => Err(SolidityError::UnsatisfiableVersion(constraints.join(", "))),
+        }
+    }
+
+    /// Ask providers in order for a compiler of `version`
+    fn find_compiler(&self, version: &str) -> std::result::Result<PathBuf, SolidityError> {
+        for provider in self.providers.iter() {
+            if let Some(compiler_path) = provider.provide(version)? {
+                return Ok(compiler_path);
+            }
+        }
+        Err(SolidityError::CompilerNotFound(version.to_string()))
+    }
+
+    /// Build the input of `--standard-json` mode
+    fn standard_json_input(&self, version: &str) -> std::result::Result<String, SolidityError> {
+        let path = self.option.contract.to_string_lossy().to_string();
+        let content = fs::read_to_string(self.option.contract)?;
+        // legacyAST is removed since 0.8.0
+        let ast_kind = match Version::parse(version) {
+            Some(version) if version < Version::new(0, 8, 0) => "legacyAST",
+            _ => "ast",
+        };
+        let mut input = json::JsonValue::new_object();
+        input["language"] = "Solidity".into();
+        input["sources"][path.as_str()]["content"] = content.into();
+        input["settings"]["remappings"] = self.option.remappings.clone().into();
+        input["settings"]["optimizer"]["enabled"] = self.option.optimizer.is_some().into();
+        input["settings"]["optimizer"]["runs"] = self.option.optimizer.unwrap_or(200).into();
+        input["settings"]["outputSelection"]["*"][""] = vec![ast_kind].into();
+        Ok(input.dump())
+    }
+
+    fn compile_standard_json(&self, compiler_path: &Path, version: &str) -> std::result::Result<SolidityOutput, SolidityError> {
+        let input = self.standard_json_input(version)?;
+        let mut child = Command::new(compiler_path)
+            .arg("--standard-json")
+            .args(self.import_args(version))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(stdin) = child.stdin.as_mut() {
+            stdin.write_all(input.as_bytes())?;
+        }
+        let Output { stdout, stderr, .. } = child.wait_with_output()?;
+        let ast = String::from_utf8(stdout).map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+        let output = match json::parse(&ast) {
+            Ok(output) => output,
+            Err(_) => return Err(SolidityError::Compiler(vec![CompilerMessage::from_stderr(&stderr)])),
+        };
+        let messages = CompilerMessage::from_output(&output);
+        if messages.iter().any(|message| message.severity == MessageSeverity::Error) {
+            return Err(SolidityError::Compiler(messages));
+        }
+        let paths = output["sources"].entries().map(|(path, _)| path).collect();
+        let sources = self.read_sources(paths)?;
+        let standard_json_output = SolidityStandardJsonOutput {
+            ast,
+            sources,
+            messages,
+        };
+        Ok(SolidityOutput::StandardJson(standard_json_output))
+    }
+
+    fn compile_combined_json(&self, compiler_path: &Path, version: &str) -> std::result::Result<SolidityOutput, SolidityError> {
+        // The AST is always compact since 0.8.0 which removes `compact-format`
+        let fields = match (&self.option.kind, Version::parse(version)) {
+            (SolidityOutputKind::CompactAST, Some(version)) if version < Version::new(0, 8, 0) => "ast,compact-format",
+            _ => "ast",
+        };
         let Output { stdout, status, stderr } = Command::new(compiler_path)
             .arg("--combined-json")
-            .arg("ast")
+            .arg(fields)
+            .args(self.import_args(version))
+            .args(self.option.remappings.iter())
             .arg(self.option.contract)
             .output()?;
-        assert!(status.success(), String::from_utf8(stderr).unwrap());
+        if !status.success() {
+            return Err(SolidityError::Compiler(vec![CompilerMessage::from_stderr(&stderr)]));
+        }
+        let ast = String::from_utf8(stdout).map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+        let output = json::parse(&ast).map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+        let paths = output["sourceList"].members().filter_map(|path| path.as_str()).collect();
+        let sources = self.read_sources(paths)?;
+        let ast_output = SolidityASTOutput {
+            sources,
+            ast,
+        };
+        Ok(SolidityOutput::AST(ast_output))
+    }
+
+    pub fn compile(&self) -> std::result::Result<SolidityOutput, SolidityError> {
+        let version = self.extract_version()?;
+        let compiler_path = self.find_compiler(&version)?;
         match self.option.kind {
-            SolidityOutputKind::AST => {
-                let ast = String::from_utf8(stdout).expect("Invalid utf8 format");
-                let sources = self.extract_source_map(&ast)?; 
-                let ast_output = SolidityASTOutput {
-                    sources,
-                    ast,
-                };
-                Ok(SolidityOutput::AST(ast_output))
-            }
+            SolidityOutputKind::AST | SolidityOutputKind::CompactAST => {
+                self.compile_combined_json(&compiler_path, &version)
+            },
+            SolidityOutputKind::StandardJson => self.compile_standard_json(&compiler_path, &version),
         }
     }
 }
@@ -0,0 +1,212 @@
+use std::fmt;
+use std::cmp::Ordering;
+use regex::Regex;
+
+/// Version of a compiler release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Version { major, minor, patch }
+    }
+
+    /// Parse `0.4.24` or a release tag `v0.4.24`
+    pub fn parse(version: &str) -> Option<Self> {
+        let parts = version.trim()
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| part.parse::<u32>().ok())
+            .collect::<Option<Vec<u32>>>()?;
+        match parts.as_slice() {
+            [major, minor, patch] => Some(Version::new(*major, *minor, *patch)),
+            _ => None,
+        }
+    }
+
+    /// Name of the release tag, e.g `v0.4.24`
+    pub fn tag(&self) -> String {
+        format!("v{}", self)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Primitive comparison against a version
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Comparator {
+    Greater(Version),
+    GreaterEq(Version),
+    Less(Version),
+    LessEq(Version),
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        match self {
+            Comparator::Greater(other) => version.cmp(other) == Ordering::Greater,
+            Comparator::GreaterEq(other) => version.cmp(other) != Ordering::Less,
+            Comparator::Less(other) => version.cmp(other) == Ordering::Less,
+            Comparator::LessEq(other) => version.cmp(other) != Ordering::Greater,
+        }
+    }
+
+    /// Smallest version matching a `>` or `>=` comparator
+    fn lower_bound(&self) -> Option<Version> {
+        match self {
+            Comparator::Greater(other) => Some(Version::new(other.major, other.minor, other.patch + 1)),
+            Comparator::GreaterEq(other) => Some(*other),
+            _ => None,
+        }
+    }
+}
+
+/// Version constraint of a `pragma solidity` directive
+///
+/// The constraint is a list of alternatives separated by `||`, each alternative is a list of
+/// comparators that must hold together:
+///
+/// ```javascript
+/// pragma solidity ^0.4.24;
+/// pragma solidity >=0.4.22 <0.6.0;
+/// pragma solidity 0.4.0 - 0.4.26 || =0.5.1;
+/// ```
+///
+/// Partial versions and wildcards (`0.4`, `0.4.x`, `*`) follow the semantics of npm semver which
+/// is used by the compiler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    alternatives: Vec<Vec<Comparator>>,
+}
+
+impl VersionReq {
+    /// Parse the constraint part of a pragma, e.g `>=0.4.22 <0.6.0`
+    pub fn parse(req: &str) -> Option<Self> {
+        let hyphen_regex = Regex::new(r"^(\S+)\s+-\s+(\S+)$").unwrap();
+        let comparator_regex = Regex::new(r"^(\^|~|>=|<=|>|<|=)?\s*([0-9xX*]+(\.[0-9xX*]+){0,2})").unwrap();
+        let mut alternatives = vec![];
+        for alternative in req.split("||") {
+            let alternative = alternative.trim();
+            let mut comparators = vec![];
+            if let Some(cap) = hyphen_regex.captures(alternative) {
+                let (from, _) = VersionReq::partial(&cap[1])?;
+                let (to, exact) = VersionReq::partial(&cap[2])?;
+                comparators.push(Comparator::GreaterEq(VersionReq::floor(&from)));
+                comparators.push(VersionReq::upper_bound(&to, exact));
+            } else {
+                let mut rest = alternative;
+                while !rest.is_empty() {
+                    let cap = comparator_regex.captures(rest)?;
+                    let op = cap.get(1).map(|op| op.as_str()).unwrap_or("=");
+                    let (parts, exact) = VersionReq::partial(&cap[2])?;
+                    comparators.append(&mut VersionReq::expand(op, &parts, exact));
+                    rest = rest[cap[0].len()..].trim_start();
+                }
+            }
+            alternatives.push(comparators);
+        }
+        Some(VersionReq { alternatives })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.alternatives.iter().any(|comparators| {
+            comparators.iter().all(|comparator| comparator.matches(version))
+        })
+    }
+
+    /// Oldest version satisfying all `reqs`
+    ///
+    /// The oldest version is one of the lower bounds of the alternatives, so only they are tried
+    pub fn lowest(reqs: &[VersionReq]) -> Option<Version> {
+        let mut bounds = reqs.iter()
+            .flat_map(|req| req.alternatives.iter().flatten())
+            .filter_map(|comparator| comparator.lower_bound())
+            .collect::<Vec<Version>>();
+        bounds.push(Version::new(0, 0, 0));
+        bounds.into_iter()
+            .filter(|version| reqs.iter().all(|req| req.matches(version)))
+            .min()
+    }
+
+    /// Split a partial version into known numbers, `exact` is true if all 3 numbers are given
+    fn partial(version: &str) -> Option<(Vec<u32>, bool)> {
+        let mut parts = vec![];
+        for part in version.split('.') {
+            match part {
+                "x" | "X" | "*" => break,
+                _ => parts.push(part.parse::<u32>().ok()?),
+            }
+        }
+        let exact = parts.len() == 3;
+        Some((parts, exact))
+    }
+
+    /// Smallest version starting with `parts`
+    fn floor(parts: &[u32]) -> Version {
+        let get = |idx: usize| parts.get(idx).cloned().unwrap_or(0);
+        Version::new(get(0), get(1), get(2))
+    }
+
+    /// Smallest version after all versions starting with `parts`
+    fn ceil(parts: &[u32]) -> Version {
+        match parts.len() {
+            1 => Version::new(parts[0] + 1, 0, 0),
+            2 => Version::new(parts[0], parts[1] + 1, 0),
+            _ => Version::new(parts[0], parts[1], parts[2] + 1),
+        }
+    }
+
+    /// `<= parts`
+    fn upper_bound(parts: &[u32], exact: bool) -> Comparator {
+        match (parts.is_empty(), exact) {
+            (true, _) => Comparator::GreaterEq(Version::new(0, 0, 0)),
+            (_, true) => Comparator::LessEq(VersionReq::floor(parts)),
+            _ => Comparator::Less(VersionReq::ceil(parts)),
+        }
+    }
+
+    /// Convert an operator and a partial version to primitive comparators
+    fn expand(op: &str, parts: &[u32], exact: bool) -> Vec<Comparator> {
+        let any = Comparator::GreaterEq(Version::new(0, 0, 0));
+        if parts.is_empty() {
+            return match op {
+                "<" | ">" => vec![Comparator::Less(Version::new(0, 0, 0))],
+                _ => vec![any],
+            };
+        }
+        let floor = VersionReq::floor(parts);
+        match op {
+            "^" => {
+                // Keep the first non-zero number
+                let ceil = match parts.iter().position(|part| *part != 0) {
+                    Some(idx) => VersionReq::ceil(&parts[..=idx]),
+                    None => VersionReq::ceil(parts),
+                };
+                vec![Comparator::GreaterEq(floor), Comparator::Less(ceil)]
+            },
+            "~" => {
+                let ceil = VersionReq::ceil(&parts[..parts.len().min(2)]);
+                vec![Comparator::GreaterEq(floor), Comparator::Less(ceil)]
+            },
+            ">=" => vec![Comparator::GreaterEq(floor)],
+            ">" => match exact {
+                true => vec![Comparator::Greater(floor)],
+                false => vec![Comparator::GreaterEq(VersionReq::ceil(parts))],
+            },
+            "<" => vec![Comparator::Less(floor)],
+            "<=" => vec![VersionReq::upper_bound(parts, exact)],
+            _ => match exact {
+                true => vec![Comparator::GreaterEq(floor), Comparator::LessEq(floor)],
+                false => vec![Comparator::GreaterEq(floor), Comparator::Less(VersionReq::ceil(parts))],
+            },
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::{ BTreeMap, BTreeSet, HashSet };
+use crate::dfg::Network;
+use crate::oracle::{
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Detector of public functions which change privileged state variables without access control
+///
+/// A state variable is privileged if its name suggests so, e.g `owner` or `paused`, or if it is
+/// an address or a mapping to bool which a condition compares with `msg.sender`. A write is
+/// reported unless it is only reached if `msg.sender` equals a state variable, checked in the
+/// function or in one of its modifiers
+pub struct AccessControlDetector;
+
+impl AccessControlDetector {
+    /// Parts of names of privileged state variables
+    const NAMES: [&'static str; 5] = ["owner", "admin", "paused", "fee", "implementation"];
+
+    /// State variables compared with `msg.sender` by any function or modifier, balances such as
+    /// `balances[msg.sender]` are not
+    fn find_owners(context: &Context) -> HashSet<u32> {
+        let mut scopes = vec![];
+        for function in context.get_functions() {
+            scopes.push(function.node.id);
+            scopes.extend(context.get_modifiers(function.node.id).iter().map(|modifier| modifier.node.id));
+        }
+        let mut owners = HashSet::new();
+        for scope_id in scopes {
+            for execution_path in context.get_execution_paths(scope_id) {
+                for vertex_id in execution_path {
+                    owners.extend(context.get_owner_states(*vertex_id));
+                }
+            }
+        }
+        owners
+    }
+
+    fn is_privileged_name(name: &str) -> bool {
+        let name = name.to_lowercase();
+        AccessControlDetector::NAMES.iter().any(|part| name.contains(part))
+    }
+}
+
+impl Detector for AccessControlDetector {
+    fn id(&self) -> &str {
+        "missing-access-control"
+    }
+
+    fn description(&self) -> &str {
+        "Anyone can change privileged state variables"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    /// The write is the primary node, the function is `function`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let owners = AccessControlDetector::find_owners(&context);
+        let privileged = context.get_states().iter()
+            .filter(|state_id| {
+                owners.contains(state_id)
+                    || AccessControlDetector::is_privileged_name(context.get_state_name(**state_id))
+            })
+            .cloned()
+            .collect::<HashSet<u32>>();
+        // Write => function and privileged state variables it writes
+        let mut writes: BTreeMap<u32, (u32, BTreeSet<u32>)> = BTreeMap::new();
+        for function in context.get_functions() {
+            let function_id = function.node.id;
+            if !context.is_public(function) || context.is_constructor(function) || context.is_read_only(function) {
+                continue;
+            }
+            for execution_path in context.get_execution_paths(function_id) {
+                for vertex_id in execution_path {
+                    if writes.contains_key(vertex_id) {
+                        continue;
+                    }
+                    let states = context.get_state_writes(*vertex_id).intersection(&privileged)
+                        .cloned()
+                        .collect::<BTreeSet<u32>>();
+                    if !states.is_empty() && !context.is_sender_checked(function_id, *vertex_id) {
+                        writes.insert(*vertex_id, (function_id, states));
+                    }
+                }
+            }
+        }
+        let mut findings = vec![];
+        for (write_id, (function_id, states)) in writes {
+            let (write, function) = match (dict.walker_at(write_id), dict.walker_at(function_id)) {
+                (Some(write), Some(function)) => (write, function),
+                _ => continue,
+            };
+            let names = states.iter()
+                .map(|state_id| format!("`{}`", context.get_state_name(*state_id)))
+                .collect::<Vec<String>>();
+            let function_name = match function.node.attributes["name"].as_str().unwrap_or("") {
+                "" => "fallback",
+                name => name,
+            };
+            let message = format!(
+                "`{}` writes {} without checking `msg.sender`",
+                function_name,
+                names.join(", "),
+            );
+            // Names are a guess, comparisons with `msg.sender` are not
+            let confidence = match states.iter().any(|state_id| owners.contains(state_id)) {
+                true => Confidence::Medium,
+                false => Confidence::Low,
+            };
+            let finding = Finding::new(self.id(), self.severity(), confidence, write, &message)
+                .with_related("function", function);
+            findings.push(finding);
+        }
+        findings
+    }
+}
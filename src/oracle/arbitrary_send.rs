@@ -0,0 +1,151 @@
+use std::collections::{ BTreeMap, HashSet };
+use crate::dfg::Network;
+use crate::core::{
+    Action,
+    Member,
+    Variable,
+    Walker,
+};
+use crate::oracle::{
+    CallKind,
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+    UnsafeSendingCondition,
+};
+
+/// Detector of ether sent to an address chosen by the caller
+///
+/// `transfer`, `send` and `call.value` are reported if the recipient depends on a parameter of a
+/// public function or `msg.data` and the call is reachable without comparing `msg.sender` with a
+/// state variable. Withdrawals such as `msg.sender.transfer(amount)` are not reported
+pub struct ArbitrarySendDetector;
+
+impl ArbitrarySendDetector {
+    /// Variables which the recipient of a call depends on
+    fn find_dependencies(
+        context: &Context,
+        network: &Network,
+        execution_path: &[u32],
+        call_id: u32,
+        target: &Walker,
+    ) -> HashSet<(Variable, u32)> {
+        let dict = context.get_dict();
+        // The recipient is read by the callee, the call itself depends on its arguments
+        let callee_id = dict.walker_at(call_id)
+            .and_then(|call| call.direct_childs(|_| true).unwrap_or_default().into_iter().next())
+            .map(|callee| callee.node.id)
+            .unwrap_or(call_id);
+        let dependencies = context.get_dependencies(callee_id, target);
+        if !dependencies.is_empty() {
+            return dependencies;
+        }
+        // The recipient of `to.call.value(amount)()` is not used by any vertex, its last
+        // definition before the call is used instead
+        let members = Variable::parse(target, dict).unwrap_or_default().iter()
+            .map(|variable| variable.get_members().clone())
+            .collect::<HashSet<Vec<Member>>>();
+        let index = execution_path.iter().position(|vertex_id| *vertex_id == call_id).unwrap_or(0);
+        for vertex_id in execution_path[..index].iter().rev() {
+            let kills = context.get_actions(*vertex_id).iter()
+                .filter_map(|action| match action {
+                    Action::Kill(variable, _) if members.contains(variable.get_members()) => Some(variable),
+                    _ => None,
+                })
+                .collect::<Vec<&Variable>>();
+            if kills.is_empty() {
+                continue;
+            }
+            let mut dependencies = kills.into_iter()
+                .map(|variable| (variable.clone(), *vertex_id))
+                .collect::<HashSet<(Variable, u32)>>();
+            for action in context.get_actions(*vertex_id) {
+                if let Action::Use(variable, _) = action {
+                    let source = (variable.clone(), *vertex_id);
+                    dependencies.extend(network.traverse(source).into_iter().flatten());
+                }
+            }
+            return dependencies;
+        }
+        HashSet::new()
+    }
+}
+
+impl Detector for ArbitrarySendDetector {
+    fn id(&self) -> &str {
+        "arbitrary-send"
+    }
+
+    fn description(&self) -> &str {
+        "Anyone can send ether to an arbitrary address"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    /// The call is the primary node, the vertex reading the input is `recipient`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let parameters = context.get_public_parameters();
+        // Call => input which the recipient depends on and the vertex reading it
+        let mut sends: BTreeMap<u32, (String, u32)> = BTreeMap::new();
+        for function in context.get_functions() {
+            let function_id = function.node.id;
+            if !context.is_public(function) || context.is_constructor(function) {
+                continue;
+            }
+            for execution_path in context.get_execution_paths(function_id) {
+                for vertex_id in execution_path {
+                    let call_site = match dict.walker_at(*vertex_id).and_then(CallSite::new) {
+                        Some(call_site) => call_site,
+                        None => continue,
+                    };
+                    let is_send = call_site.sends_value()
+                        && call_site.get_kind() != CallKind::Selfdestruct
+                        && UnsafeSendingCondition::SENDING_METHODS.contains(&call_site.get_member());
+                    if !is_send || sends.contains_key(vertex_id) {
+                        continue;
+                    }
+                    let target = match call_site.get_target().and_then(|id| dict.walker_at(id)) {
+                        Some(target) => target,
+                        None => continue,
+                    };
+                    if target.node.source.trim() == "msg.sender" {
+                        continue;
+                    }
+                    let dependencies = ArbitrarySendDetector::find_dependencies(&context, network, execution_path, *vertex_id, target);
+                    let input = match Context::find_input(&dependencies, &parameters) {
+                        Some(input) => input,
+                        None => continue,
+                    };
+                    if !context.is_sender_checked(function_id, *vertex_id) {
+                        sends.insert(*vertex_id, input);
+                    }
+                }
+            }
+        }
+        let mut findings = vec![];
+        for (call_id, (input, source_id)) in sends {
+            let call = match dict.walker_at(call_id) {
+                Some(walker) => walker,
+                None => continue,
+            };
+            let member = CallSite::new(call)
+                .map(|call_site| call_site.get_member().to_string())
+                .unwrap_or_default();
+            let message = format!("`{}` sends ether to a recipient which depends on `{}`", member, input);
+            let finding = Finding::new(self.id(), self.severity(), Confidence::Medium, call, &message);
+            let finding = match dict.walker_at(source_id) {
+                Some(source) if source_id != call_id => finding.with_related("recipient", source),
+                _ => finding,
+            };
+            findings.push(finding);
+        }
+        findings
+    }
+}
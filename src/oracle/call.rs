@@ -0,0 +1,192 @@
+use crate::core::Walker;
+
+/// Kind of a function call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallKind {
+    /// `call`, `callcode`, `delegatecall` or `staticcall` of an address
+    LowLevel,
+    /// `send` of an address
+    Send,
+    /// `transfer` of an address
+    Transfer,
+    /// A function of another contract
+    External,
+    /// `selfdestruct` or `suicide`
+    Selfdestruct,
+    /// `.value(..)` or `.gas(..)` which only sets an option of another call
+    Option,
+    /// Internal, library and builtin functions, events and type conversions
+    Internal,
+}
+
+/// A FunctionCall node classified by what it calls
+///
+/// Options of a call are skipped, `a.call.value(1)(data)` is a low-level `call` of `a` sending
+/// `1` and `a.call{ value: 1 }(data)` is the same call in the compact AST
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    id: u32,
+    kind: CallKind,
+    member: String,
+    sends_value: bool,
+    target: Option<u32>,
+    value: Option<u32>,
+    arguments: Vec<u32>,
+}
+
+impl CallSite {
+    /// Classify a FunctionCall node, other nodes are not calls
+    pub fn new(walker: &Walker) -> Option<Self> {
+        if walker.node.name != "FunctionCall" {
+            return None;
+        }
+        let children = walker.direct_childs(|_| true).unwrap_or_default();
+        let mut callee = children.first()?.clone();
+        let mut call_site = CallSite {
+            id: walker.node.id,
+            kind: CallKind::Internal,
+            member: String::new(),
+            sends_value: false,
+            target: None,
+            value: None,
+            arguments: children[1..].iter().map(|walker| walker.node.id).collect(),
+        };
+        if walker.node.attributes["type_conversion"].as_bool().unwrap_or(false) {
+            return Some(call_site);
+        }
+        if CallSite::is_option(&callee) {
+            call_site.kind = CallKind::Option;
+            return Some(call_site);
+        }
+        loop {
+            let callee_children = callee.direct_childs(|_| true).unwrap_or_default();
+            match callee.node.name {
+                "FunctionCall" if callee_children.first().map(CallSite::is_option).unwrap_or(false) => {
+                    let option = &callee_children[0];
+                    if option.node.attributes["member_name"].as_str() == Some("value") {
+                        call_site.sends_value = true;
+                        call_site.value = callee_children.get(1).map(|walker| walker.node.id);
+                    }
+                    match option.direct_childs(|_| true).unwrap_or_default().into_iter().next() {
+                        Some(walker) => callee = walker,
+                        None => break,
+                    }
+                },
+                "FunctionCallOptions" => {
+                    let names = &callee.node.attributes["names"];
+                    for (name, option) in names.members().zip(callee_children.iter().skip(1)) {
+                        if name == "value" {
+                            call_site.sends_value = true;
+                            call_site.value = Some(option.node.id);
+                        }
+                    }
+                    match callee_children.into_iter().next() {
+                        Some(walker) => callee = walker,
+                        None => break,
+                    }
+                },
+                _ => break,
+            }
+        }
+        call_site.update(&callee);
+        Some(call_site)
+    }
+
+    /// `.value` or `.gas` of a function
+    fn is_option(walker: &Walker) -> bool {
+        let member_name = walker.node.attributes["member_name"].as_str();
+        walker.node.name == "MemberAccess"
+            && (member_name == Some("value") || member_name == Some("gas"))
+            && walker.direct_childs(|_| true).unwrap_or_default().first()
+                .and_then(|walker| walker.node.attributes["type"].as_str())
+                .map(|kind| kind.starts_with("function"))
+                .unwrap_or(false)
+    }
+
+    /// Find the kind, the target and the member from the callee without options
+    fn update(&mut self, callee: &Walker) {
+        match callee.node.name {
+            "MemberAccess" => {
+                let member_name = callee.node.attributes["member_name"].as_str().unwrap_or("");
+                let reference = callee.node.attributes["referencedDeclaration"].as_u32();
+                let base = match callee.direct_childs(|_| true).unwrap_or_default().into_iter().next() {
+                    Some(base) => base,
+                    None => return,
+                };
+                let base_kind = base.node.attributes["type"].as_str().unwrap_or("");
+                let base_name = base.node.attributes["value"].as_str().unwrap_or("");
+                let is_address = base_kind.starts_with("address") || base_kind.starts_with("contract ");
+                self.member = member_name.to_string();
+                self.target = Some(base.node.id);
+                self.kind = match (reference, member_name) {
+                    (None, "call") | (None, "callcode") | (None, "delegatecall") | (None, "staticcall") if is_address => {
+                        CallKind::LowLevel
+                    },
+                    (None, "send") if is_address => CallKind::Send,
+                    (None, "transfer") if is_address => CallKind::Transfer,
+                    _ => match base.node.name == "Identifier" && (base_name == "this" || base_name == "super") {
+                        true => CallKind::Internal,
+                        false => match base_kind.starts_with("contract ") {
+                            true => CallKind::External,
+                            false => CallKind::Internal,
+                        },
+                    },
+                };
+                if self.kind == CallKind::Send || self.kind == CallKind::Transfer {
+                    self.sends_value = true;
+                    self.value = self.arguments.first().cloned();
+                }
+                if self.kind == CallKind::Internal {
+                    self.target = None;
+                }
+            },
+            "Identifier" => {
+                let name = callee.node.attributes["value"].as_str().unwrap_or("");
+                self.member = name.to_string();
+                if name == "selfdestruct" || name == "suicide" {
+                    self.kind = CallKind::Selfdestruct;
+                    self.sends_value = true;
+                    self.target = self.arguments.first().cloned();
+                }
+            },
+            _ => {},
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_kind(&self) -> CallKind {
+        self.kind
+    }
+
+    /// Name of the called function, e.g `call`, `transfer` or `withdraw`
+    pub fn get_member(&self) -> &str {
+        &self.member
+    }
+
+    /// Whether the call may send ether
+    pub fn sends_value(&self) -> bool {
+        self.sends_value
+    }
+
+    /// Node of the called address or contract, the beneficiary of selfdestruct
+    pub fn get_target(&self) -> Option<u32> {
+        self.target
+    }
+
+    /// Node of the amount of ether
+    pub fn get_value(&self) -> Option<u32> {
+        self.value
+    }
+
+    pub fn get_arguments(&self) -> &Vec<u32> {
+        &self.arguments
+    }
+
+    /// Whether the call gives control to another contract
+    pub fn is_external(&self) -> bool {
+        matches!(self.kind, CallKind::LowLevel | CallKind::Send | CallKind::Transfer | CallKind::External)
+    }
+}
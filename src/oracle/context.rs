@@ -0,0 +1,540 @@
+use std::collections::{ HashMap, HashSet };
+use crate::dfg::Network;
+use crate::loader::{ Version, VersionReq };
+use crate::oracle::{ CallKind, CallSite };
+use crate::core::{
+    Action,
+    Dictionary,
+    Member,
+    SmartContractQuery,
+    Utils,
+    Variable,
+    Walker,
+};
+
+/// Facts of a network which are shared by detectors
+///
+/// Actions of all data flow graphs are merged so that a vertex is looked up without knowing its
+/// function. `Dictionary::new` checks every node of the network, detectors walk them without
+/// expecting errors
+pub struct Context<'a, 'b> {
+    network: &'a Network<'b>,
+    actions: HashMap<u32, Vec<Action>>,
+    states: Vec<u32>,
+    /// Conditions of if and loop statements and their statements
+    conditions: HashMap<u32, u32>,
+}
+
+impl<'a, 'b> Context<'a, 'b> {
+    pub fn new(network: &'a Network<'b>) -> Self {
+        let dict = network.get_dict();
+        let mut actions = HashMap::new();
+        for (_, dfg) in network.get_dfgs().iter() {
+            actions.extend(dfg.get_new_actions().clone());
+        }
+        let mut conditions = HashMap::new();
+        for name in ["IfStatement", "WhileStatement", "DoWhileStatement", "ForStatement"].iter() {
+            for statement in dict.filter_by(name) {
+                if let Some(condition) = Context::get_statement_condition(statement) {
+                    conditions.insert(condition.node.id, statement.node.id);
+                }
+            }
+        }
+        let contract_id = network.get_contract_id();
+        let states = dict.find_ids(SmartContractQuery::StatesByContractId(contract_id));
+        Context { network, actions, states, conditions }
+    }
+
+    /// Condition of an if or a loop statement, it is the only child which is not a statement
+    pub fn get_statement_condition<'c>(statement: &Walker<'c>) -> Option<Walker<'c>> {
+        statement.direct_childs(|_| true).unwrap_or_default().into_iter().find(|walker| !Context::is_statement(walker))
+    }
+
+    fn is_statement(walker: &Walker) -> bool {
+        let name = walker.node.name;
+        name == "Block"
+            || name.ends_with("Statement")
+            || matches!(name, "Return" | "Throw" | "Break" | "Continue" | "InlineAssembly")
+    }
+
+    pub fn get_dict(&self) -> &Dictionary<'_> {
+        self.network.get_dict()
+    }
+
+    /// State variables of the contract and its parents in declaration order
+    pub fn get_states(&self) -> &Vec<u32> {
+        &self.states
+    }
+
+    /// Implemented functions of the contract and its parents
+    pub fn get_functions(&self) -> Vec<&Walker<'_>> {
+        let contract_id = self.network.get_contract_id();
+        self.get_dict().find_walkers(SmartContractQuery::FunctionsByContractId(contract_id))
+            .into_iter()
+            .filter(|walker| walker.node.name == "FunctionDefinition")
+            .filter(|walker| walker.node.attributes["implemented"].as_bool().unwrap_or(true))
+            .collect()
+    }
+
+    /// Definitions of modifiers invoked by a function
+    pub fn get_modifiers(&self, function_id: u32) -> Vec<&Walker<'_>> {
+        self.get_modifier_invocations(function_id).into_iter()
+            .map(|(_, definition)| definition)
+            .collect()
+    }
+
+    /// Modifier invocations of a function and the definitions they invoke, arguments of an
+    /// invocation follow its name
+    pub fn get_modifier_invocations(&self, function_id: u32) -> Vec<(Walker<'_>, &Walker<'_>)> {
+        let dict = self.get_dict();
+        let mut invocations = vec![];
+        if let Some(function) = dict.walker_at(function_id) {
+            for invocation in function.direct_childs(|walker| walker.node.name == "ModifierInvocation").unwrap_or_default() {
+                let definition = invocation.direct_childs(|_| true).unwrap_or_default().first()
+                    .and_then(|walker| walker.node.attributes["referencedDeclaration"].as_u32())
+                    .and_then(|id| dict.walker_at(id))
+                    .filter(|walker| walker.node.name == "ModifierDefinition");
+                if let Some(definition) = definition {
+                    invocations.push((invocation, definition));
+                }
+            }
+        }
+        invocations
+    }
+
+    /// Execution paths of a function or a modifier
+    pub fn get_execution_paths(&self, function_id: u32) -> &[Vec<u32>] {
+        self.network.get_dfgs().get(&function_id)
+            .map(|dfg| dfg.get_cfg().get_execution_paths().as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Parameters of a function or a modifier
+    pub fn get_parameters(&self, function_id: u32) -> &[u32] {
+        self.network.get_dfgs().get(&function_id)
+            .and_then(|dfg| dfg.get_cfg().get_parameters().get(&function_id))
+            .map(|parameters| parameters.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Parameters of public and external functions, their values are chosen by the caller
+    pub fn get_public_parameters(&self) -> HashSet<u32> {
+        self.get_functions().into_iter()
+            .filter(|function| self.is_public(function))
+            .flat_map(|function| self.get_parameters(function.node.id).to_vec())
+            .collect()
+    }
+
+    /// Input of the caller among dependencies, `msg.data` or a parameter in `parameters`, and the
+    /// vertex reading it
+    pub fn find_input(dependencies: &HashSet<(Variable, u32)>, parameters: &HashSet<u32>) -> Option<(String, u32)> {
+        let mut inputs = dependencies.iter()
+            .filter(|(variable, _)| match variable.get_members().first() {
+                Some(Member::Global(_)) => variable.get_unflat() == "msg.data",
+                Some(Member::Reference(id)) => parameters.contains(id),
+                _ => false,
+            })
+            .map(|(variable, vertex_id)| (variable.get_unflat().to_string(), *vertex_id))
+            .collect::<Vec<(String, u32)>>();
+        inputs.sort();
+        inputs.into_iter().next()
+    }
+
+    /// Condition checked at a vertex, the expression of an if, a loop, a `require` or an `assert`
+    pub fn get_condition(&self, vertex_id: u32) -> Option<Walker<'_>> {
+        let walker = self.get_dict().walker_at(vertex_id)?;
+        if self.conditions.contains_key(&vertex_id) {
+            return Some(walker.clone());
+        }
+        let call_site = CallSite::new(walker)?;
+        match (call_site.get_kind(), call_site.get_member()) {
+            (CallKind::Internal, "require") | (CallKind::Internal, "assert") => {
+                walker.direct_childs(|_| true).unwrap_or_default().into_iter().nth(1)
+            },
+            _ => None,
+        }
+    }
+
+    /// Condition of a `require` or an `assert` right after the vertex at `index` of an execution
+    /// path, no other statement is between them
+    pub fn find_next_assertion(&self, execution_path: &[u32], index: usize) -> Option<Walker<'_>> {
+        let dict = self.get_dict();
+        for vertex_id in execution_path.iter().skip(index + 1) {
+            if !self.conditions.contains_key(vertex_id) {
+                if let Some(condition) = self.get_condition(*vertex_id) {
+                    return Some(condition);
+                }
+            }
+            if dict.walker_at(*vertex_id).map(Context::is_statement).unwrap_or(true) {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// Innermost vertex of an execution path containing a node, as an index of the path
+    pub fn find_vertex(&self, execution_path: &[u32], walker: &Walker) -> Option<usize> {
+        let range = walker.node.get_range();
+        execution_path.iter().enumerate()
+            .filter_map(|(index, vertex_id)| self.get_dict().walker_at(*vertex_id).map(|vertex| (index, vertex)))
+            .filter(|(_, vertex)| vertex.node.file == walker.node.file)
+            .filter(|(_, vertex)| {
+                let vertex_range = vertex.node.get_range();
+                vertex_range.start <= range.start && range.end <= vertex_range.end
+            })
+            .min_by_key(|(_, vertex)| vertex.node.get_range().len())
+            .map(|(index, _)| index)
+    }
+
+    /// Data flow paths from the variables of an expression at a vertex to their definitions, the
+    /// first item of a path is the variable of the expression. Calls and index accesses are
+    /// vertices themselves, their actions are also used
+    pub fn get_dependent_paths(&self, vertex_id: u32, expression: &Walker) -> Vec<Vec<(Variable, u32)>> {
+        let members = Variable::parse(expression, self.get_dict()).unwrap_or_default().iter()
+            .map(|variable| variable.get_members().clone())
+            .collect::<HashSet<Vec<Member>>>();
+        let mut dependent_paths = vec![];
+        let mut vertex_ids = vec![vertex_id];
+        if expression.node.id != vertex_id {
+            vertex_ids.push(expression.node.id);
+        }
+        for vertex_id in vertex_ids {
+            for action in self.get_actions(vertex_id) {
+                if let Action::Use(variable, _) = action {
+                    if members.contains(variable.get_members()) {
+                        let source = (variable.clone(), vertex_id);
+                        dependent_paths.extend(self.network.traverse(source));
+                    }
+                }
+            }
+        }
+        dependent_paths
+    }
+
+    /// Variables which an expression at a vertex depends on through data links, including the
+    /// variables of the expression
+    pub fn get_dependencies(&self, vertex_id: u32, expression: &Walker) -> HashSet<(Variable, u32)> {
+        self.get_dependent_paths(vertex_id, expression).into_iter().flatten().collect()
+    }
+
+    /// Whether a state variable can tell who is privileged, an address or a mapping to bool.
+    /// Balances such as `balances[msg.sender]` can not
+    pub fn is_owner_state(&self, state_id: u32) -> bool {
+        self.get_dict().walker_at(state_id)
+            .map(|state| {
+                let kind = Utils::normalize_kind(state).replace(' ', "");
+                kind.starts_with("address") || kind.ends_with("=>bool)")
+            })
+            .unwrap_or(false)
+    }
+
+    /// State variables which a condition at a vertex compares with `msg.sender`, e.g `owner` of
+    /// `msg.sender != owner` and `admins` of `admins[msg.sender]`
+    pub fn get_owner_states(&self, vertex_id: u32) -> HashSet<u32> {
+        let condition = match self.get_condition(vertex_id) {
+            Some(condition) => condition,
+            None => return HashSet::new(),
+        };
+        let mut owners = self.find_guarded_states(condition.node.id, &condition, true);
+        owners.extend(self.find_guarded_states(condition.node.id, &condition, false));
+        owners
+    }
+
+    /// State variables which equal `msg.sender` if an expression of a condition evaluates to
+    /// `expected`, e.g `owner` of `msg.sender == owner` if it is true and of `msg.sender != owner`
+    /// if it is false
+    fn find_guarded_states(&self, condition_id: u32, expression: &Walker, expected: bool) -> HashSet<u32> {
+        let mut owners = HashSet::new();
+        let operator = expression.node.attributes["operator"].as_str().unwrap_or("");
+        let operands = expression.direct_childs(|_| true).unwrap_or_default();
+        // Addresses are flattened to their members, e.g `msg.sender.balance`
+        let is_sender = |operand: &Walker| {
+            operand.node.source.trim() == "msg.sender"
+                || self.get_dependencies(condition_id, operand).iter()
+                    .any(|(variable, _)| variable.get_unflat() == "msg.sender")
+        };
+        match (expression.node.name, operator, operands.as_slice()) {
+            ("TupleExpression", _, [operand]) => {
+                owners.extend(self.find_guarded_states(condition_id, operand, expected));
+            },
+            ("UnaryOperation", "!", [operand]) => {
+                owners.extend(self.find_guarded_states(condition_id, operand, !expected));
+            },
+            // Both operands hold if `&&` is true, neither does if `||` is false
+            ("BinaryOperation", "&&", [left, right]) | ("BinaryOperation", "||", [left, right])
+                if expected == (operator == "&&") => {
+                owners.extend(self.find_guarded_states(condition_id, left, expected));
+                owners.extend(self.find_guarded_states(condition_id, right, expected));
+            },
+            // Either operand holds, each of them must compare `msg.sender`
+            ("BinaryOperation", "&&", [left, right]) | ("BinaryOperation", "||", [left, right]) => {
+                let left = self.find_guarded_states(condition_id, left, expected);
+                let right = self.find_guarded_states(condition_id, right, expected);
+                if !left.is_empty() && !right.is_empty() {
+                    owners.extend(left);
+                    owners.extend(right);
+                }
+            },
+            ("BinaryOperation", "==", [left, right]) | ("BinaryOperation", "!=", [left, right])
+                if expected == (operator == "==") => {
+                for (sender, other) in [(left, right), (right, left)].iter() {
+                    if is_sender(sender) {
+                        owners.extend(self.get_dependencies(condition_id, other).iter()
+                            .filter_map(|(variable, _)| self.get_state(variable)));
+                    }
+                }
+            },
+            ("IndexAccess", _, [_, index]) if expected && is_sender(index) => {
+                let reference = Utils::find_root_walker(expression, self.get_dict()).ok()
+                    .and_then(|base| base.node.attributes["referencedDeclaration"].as_u32());
+                owners.extend(reference.filter(|reference| self.states.contains(reference)));
+            },
+            _ => {},
+        }
+        owners.retain(|state_id| self.is_owner_state(*state_id));
+        owners
+    }
+
+    /// Whether a statement always reverts, e.g `revert();`, `throw;` or a block containing one
+    fn reverts(statement: &Walker) -> bool {
+        match statement.node.name {
+            "Throw" => true,
+            "Block" => statement.direct_childs(|_| true).unwrap_or_default().iter().any(Context::reverts),
+            "ExpressionStatement" => {
+                statement.direct_childs(|_| true).unwrap_or_default().first()
+                    .and_then(CallSite::new)
+                    .map(|call_site| call_site.get_kind() == CallKind::Internal && call_site.get_member() == "revert")
+                    .unwrap_or(false)
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether the vertex at `index` of an execution path is only reached if `msg.sender` equals a
+    /// state variable
+    fn is_guarded(&self, execution_path: &[u32], index: usize) -> bool {
+        self.is_reached_if(execution_path, index, |condition, expected| {
+            !self.find_guarded_states(condition.node.id, condition, expected).is_empty()
+        })
+    }
+
+    /// Whether the vertex at `index` of an execution path is only reached if `holds` accepts a
+    /// condition before it and the value the condition has: a `require` or an `assert` holds, an
+    /// if either reverts unless the condition has the value or contains the vertex in the branch
+    /// taken for it
+    pub fn is_reached_if<F>(&self, execution_path: &[u32], index: usize, holds: F) -> bool where F: Fn(&Walker, bool) -> bool {
+        let dict = self.get_dict();
+        let vertex = match dict.walker_at(execution_path[index]) {
+            Some(vertex) => vertex,
+            None => return false,
+        };
+        let range = vertex.node.get_range();
+        execution_path[..index].iter().any(|vertex_id| {
+            let condition = match self.get_condition(*vertex_id) {
+                Some(condition) => condition,
+                None => return false,
+            };
+            let guarded = |expected: bool| holds(&condition, expected);
+            let statement = match self.conditions.get(vertex_id) {
+                Some(statement_id) => dict.walker_at(*statement_id),
+                // `require` and `assert` revert unless the condition holds
+                None => return guarded(true),
+            };
+            let statement = match statement {
+                Some(statement) if statement.node.name == "IfStatement" => statement,
+                _ => return false,
+            };
+            // The first branch is taken if the condition holds, the second one otherwise
+            let branches = statement.direct_childs(|_| true).unwrap_or_default().into_iter()
+                .filter(|walker| Context::is_statement(walker))
+                .collect::<Vec<Walker>>();
+            branches.iter().enumerate().any(|(branch_index, branch)| {
+                let taken = branch_index == 0;
+                let branch_range = branch.node.get_range();
+                let contains = branch.node.file == vertex.node.file
+                    && branch_range.start <= range.start
+                    && range.end <= branch_range.end;
+                (contains && guarded(taken)) || (Context::reverts(branch) && guarded(!taken))
+            })
+        })
+    }
+
+    /// Whether `msg.sender` equals a state variable whenever a vertex is reached on every execution
+    /// path of a function, a modifier checking it before its placeholder protects the whole
+    /// function. A vertex found on no execution path is not checked
+    pub fn is_sender_checked(&self, function_id: u32, vertex_id: u32) -> bool {
+        for modifier in self.get_modifiers(function_id) {
+            let placeholders = self.find_placeholders(modifier.node.id);
+            if !placeholders.is_empty() && placeholders.iter().all(|(execution_path, index)| self.is_guarded(execution_path, *index)) {
+                return true;
+            }
+        }
+        let mut occurrences = self.get_execution_paths(function_id).iter()
+            .filter_map(|execution_path| {
+                execution_path.iter()
+                    .position(|id| *id == vertex_id)
+                    .map(|index| (execution_path, index))
+            })
+            .peekable();
+        occurrences.peek().is_some() && occurrences.all(|(execution_path, index)| self.is_guarded(execution_path, index))
+    }
+
+    /// Execution paths of a modifier reaching its placeholder and the index of the placeholder
+    pub fn find_placeholders(&self, modifier_id: u32) -> Vec<(&[u32], usize)> {
+        let dict = self.get_dict();
+        self.get_execution_paths(modifier_id).iter()
+            .filter_map(|execution_path| {
+                execution_path.iter()
+                    .position(|vertex_id| {
+                        dict.walker_at(*vertex_id)
+                            .map(|walker| walker.node.name == "PlaceholderStatement")
+                            .unwrap_or(false)
+                    })
+                    .map(|index| (execution_path.as_slice(), index))
+            })
+            .collect()
+    }
+
+    pub fn get_actions(&self, vertex_id: u32) -> &[Action] {
+        self.actions.get(&vertex_id).map(|actions| actions.as_slice()).unwrap_or(&[])
+    }
+
+    /// The state variable which `variable` belongs to, e.g `balances` of `balances[msg.sender]`
+    pub fn get_state(&self, variable: &Variable) -> Option<u32> {
+        match variable.get_members().first() {
+            Some(Member::Reference(id)) if self.states.contains(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub fn get_state_name(&self, state_id: u32) -> &str {
+        self.get_dict().walker_at(state_id)
+            .and_then(|walker| walker.node.attributes["name"].as_str())
+            .unwrap_or("")
+    }
+
+    /// State variables read at a vertex
+    pub fn get_state_reads(&self, vertex_id: u32) -> HashSet<u32> {
+        self.get_actions(vertex_id).iter()
+            .filter_map(|action| match action {
+                Action::Use(variable, _) => self.get_state(variable),
+                Action::Kill(_, _) => None,
+            })
+            .collect()
+    }
+
+    /// State variables written at a vertex
+    ///
+    /// Besides assignments, `++`, `--`, `delete` and `push` or `pop` of arrays are writes. The
+    /// declarations of state variables at the beginning of a function are not
+    pub fn get_state_writes(&self, vertex_id: u32) -> HashSet<u32> {
+        let dict = self.get_dict();
+        let mut writes = HashSet::new();
+        if self.states.contains(&vertex_id) {
+            return writes;
+        }
+        for action in self.get_actions(vertex_id) {
+            if let Action::Kill(variable, _) = action {
+                writes.extend(self.get_state(variable));
+            }
+        }
+        let walker = match dict.walker_at(vertex_id) {
+            Some(walker) => walker,
+            None => return writes,
+        };
+        let mut targets = vec![];
+        match walker.node.name {
+            "FunctionCall" => {
+                let callee = walker.direct_childs(|_| true).unwrap_or_default().into_iter().next()
+                    .filter(|walker| walker.node.name == "MemberAccess");
+                if let Some(callee) = callee {
+                    let member_name = callee.node.attributes["member_name"].as_str();
+                    if member_name == Some("push") || member_name == Some("pop") {
+                        targets.extend(callee.direct_childs(|_| true).unwrap_or_default().into_iter().next());
+                    }
+                }
+            },
+            _ => {
+                let ig = |walker: &Walker, _: &Vec<Walker>| walker.node.name == "FunctionCall";
+                let fi = |walker: &Walker, _: &Vec<Walker>| {
+                    let operator = walker.node.attributes["operator"].as_str();
+                    walker.node.name == "UnaryOperation"
+                        && (operator == Some("++") || operator == Some("--") || operator == Some("delete"))
+                };
+                for walker in walker.walk(false, ig, fi).unwrap_or_default() {
+                    targets.extend(walker.direct_childs(|_| true).unwrap_or_default().into_iter().next());
+                }
+            },
+        }
+        for target in targets {
+            let reference = Utils::find_root_walker(&target, dict).ok()
+                .and_then(|root| root.node.attributes["referencedDeclaration"].as_u32());
+            writes.extend(reference.filter(|reference| self.states.contains(reference)));
+        }
+        writes
+    }
+
+    /// Whether a function is a constructor, functions named after their contract are constructors
+    /// before 0.4.22
+    pub fn is_constructor(&self, function: &Walker) -> bool {
+        if function.node.attributes["isConstructor"].as_bool().unwrap_or(false) {
+            return true;
+        }
+        let name = function.node.attributes["name"].as_str().unwrap_or("");
+        function.node.attributes["scope"].as_u32()
+            .and_then(|scope| self.get_dict().walker_at(scope))
+            .and_then(|contract| contract.node.attributes["name"].as_str())
+            .map(|contract_name| !name.is_empty() && contract_name == name)
+            .unwrap_or(false)
+    }
+
+    /// Whether a function can be called by anyone, functions are public by default before 0.5.0
+    pub fn is_public(&self, function: &Walker) -> bool {
+        matches!(function.node.attributes["visibility"].as_str(), Some("public") | Some("external") | None)
+    }
+
+    /// Whether the pragma of the contract allows a compiler older than `version`, a contract
+    /// without pragma may be compiled by any compiler
+    pub fn allows_version_below(&self, version: &Version) -> bool {
+        let dict = self.get_dict();
+        let file = match dict.walker_at(self.network.get_contract_id()) {
+            Some(contract) => contract.node.file,
+            None => return true,
+        };
+        let mut requirements = vec![];
+        for pragma in dict.filter_by("PragmaDirective") {
+            let literals = pragma.node.attributes["literals"].members()
+                .filter_map(|literal| literal.as_str())
+                .collect::<Vec<&str>>();
+            if pragma.node.file != file || literals.first() != Some(&"solidity") {
+                continue;
+            }
+            // Literals are tokens, e.g `^`, `0.4` and `.24`
+            let mut requirement = String::new();
+            for literal in literals[1..].iter() {
+                if !literal.starts_with('.') && !requirement.ends_with('.') {
+                    requirement.push(' ');
+                }
+                requirement.push_str(literal);
+            }
+            requirements.extend(VersionReq::parse(requirement.trim()));
+        }
+        VersionReq::lowest(&requirements)
+            .map(|lowest| lowest < *version)
+            .unwrap_or(false)
+    }
+
+    /// Whether a function can receive ether
+    pub fn is_payable(&self, function: &Walker) -> bool {
+        function.node.attributes["stateMutability"].as_str() == Some("payable")
+            || function.node.attributes["payable"].as_bool().unwrap_or(false)
+    }
+
+    /// Whether a function can not change the state
+    pub fn is_read_only(&self, function: &Walker) -> bool {
+        let state_mutability = function.node.attributes["stateMutability"].as_str();
+        state_mutability == Some("view")
+            || state_mutability == Some("pure")
+            || function.node.attributes["constant"].as_bool().unwrap_or(false)
+    }
+}
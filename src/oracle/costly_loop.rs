@@ -0,0 +1,166 @@
+use std::collections::{ BTreeMap, BTreeSet, HashSet };
+use crate::dfg::Network;
+use crate::core::{ Utils, Walker };
+use crate::oracle::{
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Detector of loops which may run out of gas
+///
+/// A loop is reported if its bound depends on the length of a dynamic array in storage, on a
+/// mapping or on a counter increased by the contract, since these grow with the number of users.
+/// External calls, `send` and `transfer` in a loop are reported as well, one failing recipient
+/// blocks everyone after it
+pub struct CostlyLoopDetector;
+
+impl CostlyLoopDetector {
+    fn is_loop(walker: &Walker) -> bool {
+        matches!(walker.node.name, "WhileStatement" | "ForStatement" | "DoWhileStatement")
+    }
+
+    fn contains(outer: &Walker, inner: &Walker) -> bool {
+        let outer_range = outer.node.get_range();
+        let inner_range = inner.node.get_range();
+        outer.node.file == inner.node.file
+            && outer_range.start <= inner_range.start
+            && inner_range.end <= outer_range.end
+    }
+
+    /// State variables increased by `++` or `+=`, including mappings and arrays whose elements are
+    /// increased
+    fn find_counters(context: &Context) -> HashSet<u32> {
+        let dict = context.get_dict();
+        let mut counters = HashSet::new();
+        for function in context.get_functions() {
+            let ig = |_: &Walker, _: &Vec<Walker>| false;
+            let fi = |walker: &Walker, _: &Vec<Walker>| {
+                let operator = walker.node.attributes["operator"].as_str();
+                (walker.node.name == "UnaryOperation" && operator == Some("++"))
+                    || (walker.node.name == "Assignment" && operator == Some("+="))
+            };
+            for walker in function.walk(false, ig, fi).unwrap_or_default() {
+                if let Some(operand) = walker.direct_childs(|_| true).unwrap_or_default().first() {
+                    let reference = Utils::find_root_walker(operand, dict).ok()
+                        .and_then(|root| root.node.attributes["referencedDeclaration"].as_u32());
+                    counters.extend(reference.filter(|reference| context.get_states().contains(reference)));
+                }
+            }
+        }
+        counters
+    }
+
+    /// Reasons why the bound of a loop grows, e.g `the length of users`
+    fn find_bounds(context: &Context, condition: &Walker, counters: &HashSet<u32>) -> BTreeSet<String> {
+        let dict = context.get_dict();
+        let mut bounds = BTreeSet::new();
+        let dependencies = context.get_dependencies(condition.node.id, condition);
+        let mut vertex_ids = dependencies.iter().map(|(_, vertex_id)| *vertex_id).collect::<HashSet<u32>>();
+        vertex_ids.insert(condition.node.id);
+        for vertex_id in vertex_ids {
+            let walker = match dict.walker_at(vertex_id) {
+                Some(walker) => walker,
+                None => continue,
+            };
+            let ig = |_: &Walker, _: &Vec<Walker>| false;
+            let fi = |walker: &Walker, _: &Vec<Walker>| {
+                walker.node.name == "MemberAccess"
+                    && walker.node.attributes["member_name"].as_str() == Some("length")
+            };
+            for length in walker.walk(false, ig, fi).unwrap_or_default() {
+                let state = Utils::find_root_walker(&length, dict).ok()
+                    .and_then(|root| root.node.attributes["referencedDeclaration"].as_u32())
+                    .filter(|reference| context.get_states().contains(reference))
+                    .and_then(|reference| dict.walker_at(reference));
+                if let Some(state) = state {
+                    if Utils::normalize_kind(state).ends_with("[]") {
+                        bounds.insert(format!("the length of `{}`", context.get_state_name(state.node.id)));
+                    }
+                }
+            }
+        }
+        for (variable, _) in dependencies.iter() {
+            let state_id = match context.get_state(variable) {
+                Some(state_id) => state_id,
+                None => continue,
+            };
+            let is_mapping = dict.walker_at(state_id)
+                .map(|state| Utils::normalize_kind(state).starts_with("mapping"))
+                .unwrap_or(false);
+            if is_mapping {
+                bounds.insert(format!("the mapping `{}`", context.get_state_name(state_id)));
+            } else if counters.contains(&state_id) {
+                bounds.insert(format!("the counter `{}`", context.get_state_name(state_id)));
+            }
+        }
+        bounds
+    }
+}
+
+impl Detector for CostlyLoopDetector {
+    fn id(&self) -> &str {
+        "costly-loop"
+    }
+
+    fn description(&self) -> &str {
+        "Loops may run out of gas as the contract grows"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    /// The loop header is the primary node of a growing bound, the call is the primary node of a
+    /// call in a loop and the header is `loop`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let counters = CostlyLoopDetector::find_counters(&context);
+        let mut bounds: BTreeMap<u32, (Walker, BTreeSet<String>)> = BTreeMap::new();
+        // Call => header of the innermost loop
+        let mut calls: BTreeMap<u32, (Walker, Walker)> = BTreeMap::new();
+        for function in context.get_functions() {
+            let ig = |_: &Walker, _: &Vec<Walker>| false;
+            let fi = |walker: &Walker, _: &Vec<Walker>| CostlyLoopDetector::is_loop(walker);
+            let loops = function.walk(false, ig, fi).unwrap_or_default();
+            for statement in loops.iter() {
+                let header = Context::get_statement_condition(statement).unwrap_or_else(|| statement.clone());
+                let reasons = CostlyLoopDetector::find_bounds(&context, &header, &counters);
+                if !reasons.is_empty() {
+                    bounds.insert(header.node.id, (header.clone(), reasons));
+                }
+                let ig = |_: &Walker, _: &Vec<Walker>| false;
+                let fi = |walker: &Walker, _: &Vec<Walker>| {
+                    CallSite::new(walker).map(|call_site| call_site.is_external()).unwrap_or(false)
+                };
+                for call in statement.walk(false, ig, fi).unwrap_or_default() {
+                    let is_innermost = loops.iter()
+                        .filter(|other| CostlyLoopDetector::contains(statement, other) && other.node.id != statement.node.id)
+                        .all(|other| !CostlyLoopDetector::contains(other, &call));
+                    if is_innermost {
+                        calls.insert(call.node.id, (call, header.clone()));
+                    }
+                }
+            }
+        }
+        let mut findings = vec![];
+        for (_, (header, reasons)) in bounds {
+            let reasons = reasons.into_iter().collect::<Vec<String>>();
+            let message = format!("loop bound depends on {}", reasons.join(", "));
+            findings.push(Finding::new(self.id(), self.severity(), Confidence::Medium, &header, &message));
+        }
+        for (_, (call, header)) in calls {
+            let member = CallSite::new(&call)
+                .map(|call_site| call_site.get_member().to_string())
+                .unwrap_or_default();
+            let message = format!("`{}` in a loop, a failing call blocks the remaining iterations", member);
+            let finding = Finding::new(self.id(), self.severity(), Confidence::Medium, &call, &message)
+                .with_related("loop", &header);
+            findings.push(finding);
+        }
+        findings
+    }
+}
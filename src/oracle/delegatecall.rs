@@ -0,0 +1,118 @@
+use std::collections::{ BTreeMap, HashSet };
+use crate::dfg::Network;
+use crate::oracle::{
+    CallKind,
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Reasons to report a delegatecall
+#[derive(Default)]
+struct Delegatecall {
+    /// Input of the caller which the target depends on
+    target: Option<String>,
+    /// Input of the caller which the calldata depends on
+    data: Option<String>,
+    /// Whether a path reaches the call without checking `msg.sender`
+    unguarded: bool,
+}
+
+/// Detector of dangerous `delegatecall` and `callcode`
+///
+/// The callee runs with the storage and the balance of the contract. A call is reported if its
+/// target or its calldata depends on a parameter of a public function or `msg.data`, or if it is
+/// reachable without comparing `msg.sender` with a state variable
+pub struct DelegatecallDetector;
+
+impl Detector for DelegatecallDetector {
+    fn id(&self) -> &str {
+        "controlled-delegatecall"
+    }
+
+    fn description(&self) -> &str {
+        "Delegatecall depends on inputs of the caller or lacks access control"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    /// The call is the primary node
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let parameters = context.get_public_parameters();
+        let mut delegatecalls: BTreeMap<u32, Delegatecall> = BTreeMap::new();
+        for function in context.get_functions() {
+            let function_id = function.node.id;
+            if context.is_constructor(function) {
+                continue;
+            }
+            for execution_path in context.get_execution_paths(function_id) {
+                for vertex_id in execution_path {
+                    let call_site = match dict.walker_at(*vertex_id).and_then(CallSite::new) {
+                        Some(call_site) => call_site,
+                        None => continue,
+                    };
+                    let is_delegatecall = call_site.get_kind() == CallKind::LowLevel
+                        && matches!(call_site.get_member(), "delegatecall" | "callcode");
+                    if !is_delegatecall || delegatecalls.contains_key(vertex_id) {
+                        continue;
+                    }
+                    let mut delegatecall = Delegatecall::default();
+                    // The target is read by the callee, the call itself depends on its arguments
+                    let callee_id = dict.walker_at(*vertex_id)
+                        .and_then(|call| call.direct_childs(|_| true).unwrap_or_default().into_iter().next())
+                        .map(|callee| callee.node.id);
+                    let target = call_site.get_target().and_then(|id| dict.walker_at(id));
+                    if let (Some(callee_id), Some(target)) = (callee_id, target) {
+                        let dependencies = context.get_dependencies(callee_id, target);
+                        delegatecall.target = Context::find_input(&dependencies, &parameters).map(|(input, _)| input);
+                    }
+                    let mut dependencies = HashSet::new();
+                    for argument in call_site.get_arguments().iter().filter_map(|id| dict.walker_at(*id)) {
+                        dependencies.extend(context.get_dependencies(*vertex_id, argument));
+                    }
+                    delegatecall.data = Context::find_input(&dependencies, &parameters).map(|(input, _)| input);
+                    delegatecall.unguarded = context.is_public(function)
+                        && !context.is_sender_checked(function_id, *vertex_id);
+                    delegatecalls.insert(*vertex_id, delegatecall);
+                }
+            }
+        }
+        let mut findings = vec![];
+        for (call_id, delegatecall) in delegatecalls {
+            let call = match dict.walker_at(call_id) {
+                Some(walker) => walker,
+                None => continue,
+            };
+            let mut reasons = vec![];
+            if let Some(input) = &delegatecall.target {
+                reasons.push(format!("the target depends on `{}`", input));
+            }
+            if let Some(input) = &delegatecall.data {
+                reasons.push(format!("the calldata depends on `{}`", input));
+            }
+            if delegatecall.unguarded {
+                reasons.push(String::from("it is reachable without checking `msg.sender`"));
+            }
+            if reasons.is_empty() {
+                continue;
+            }
+            let severity = match delegatecall.target.is_some() && delegatecall.unguarded {
+                true => Severity::High,
+                false => Severity::Medium,
+            };
+            let member = CallSite::new(call)
+                .map(|call_site| call_site.get_member().to_string())
+                .unwrap_or_default();
+            let message = format!("`{}` is dangerous, {}", member, reasons.join(", "));
+            findings.push(Finding::new(self.id(), severity, Confidence::Medium, call, &message));
+        }
+        findings
+    }
+}
@@ -0,0 +1,17 @@
+use crate::dfg::Network;
+use crate::oracle::{ Finding, Severity };
+
+/// An analysis which reports findings of a contract
+///
+/// Detectors only read the network so that all detectors of a registry share one network
+pub trait Detector {
+    /// Unique id, e.g `reentrancy`
+    fn id(&self) -> &str;
+
+    fn description(&self) -> &str;
+
+    /// The highest severity of findings
+    fn severity(&self) -> Severity;
+
+    fn check(&self, network: &Network) -> Vec<Finding>;
+}
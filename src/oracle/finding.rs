@@ -0,0 +1,172 @@
+use std::fmt;
+use crate::core::{ Walker, Location };
+
+/// Impact of a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Informational,
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Informational => "informational",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+        }
+    }
+}
+
+/// How likely a finding is a true positive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl Confidence {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Confidence::Low => "low",
+            Confidence::Medium => "medium",
+            Confidence::High => "high",
+        }
+    }
+}
+
+/// A node of AST which is reported by an oracle
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FindingNode {
+    id: u32,
+    source: String,
+    location: Location,
+}
+
+impl FindingNode {
+    pub fn new(walker: &Walker) -> Self {
+        FindingNode {
+            id: walker.node.id,
+            source: walker.node.source.to_string(),
+            location: walker.get_location(),
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn get_location(&self) -> &Location {
+        &self.location
+    }
+
+    fn to_json(&self) -> json::JsonValue {
+        let mut value = json::JsonValue::new_object();
+        value["id"] = self.id.into();
+        value["source"] = self.source.as_str().into();
+        value["location"] = self.location.to_json();
+        value
+    }
+}
+
+/// Result of an oracle
+///
+/// The primary node is where the issue is reported, related nodes explain it and are tagged with
+/// a role, e.g `source` for where a tainted value comes from and `sink` for where it is used
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Finding {
+    detector: String,
+    severity: Severity,
+    confidence: Confidence,
+    node: FindingNode,
+    related: Vec<(String, FindingNode)>,
+    message: String,
+}
+
+impl Finding {
+    pub fn new(detector: &str, severity: Severity, confidence: Confidence, walker: &Walker, message: &str) -> Self {
+        Finding {
+            detector: detector.to_string(),
+            severity,
+            confidence,
+            node: FindingNode::new(walker),
+            related: vec![],
+            message: message.to_string(),
+        }
+    }
+
+    /// Attach a related node with its role
+    pub fn with_related(mut self, role: &str, walker: &Walker) -> Self {
+        self.related.push((role.to_string(), FindingNode::new(walker)));
+        self
+    }
+
+    pub fn get_detector(&self) -> &str {
+        &self.detector
+    }
+
+    pub fn get_severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn get_confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    pub fn get_node(&self) -> &FindingNode {
+        &self.node
+    }
+
+    pub fn get_related(&self) -> &Vec<(String, FindingNode)> {
+        &self.related
+    }
+
+    /// Related nodes having `role`
+    pub fn get_related_by_role(&self, role: &str) -> Vec<&FindingNode> {
+        self.related.iter()
+            .filter(|(related_role, _)| related_role == role)
+            .map(|(_, node)| node)
+            .collect()
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Locations of the primary node and related nodes
+    pub fn get_locations(&self) -> Vec<&Location> {
+        let mut locations = vec![self.node.get_location()];
+        locations.extend(self.related.iter().map(|(_, node)| node.get_location()));
+        locations
+    }
+
+    pub fn to_json(&self) -> json::JsonValue {
+        let mut value = json::JsonValue::new_object();
+        value["detector"] = self.detector.as_str().into();
+        value["severity"] = self.severity.as_str().into();
+        value["confidence"] = self.confidence.as_str().into();
+        value["message"] = self.message.as_str().into();
+        value["node"] = self.node.to_json();
+        value["related"] = json::JsonValue::new_array();
+        for (role, node) in self.related.iter() {
+            let mut related = node.to_json();
+            related["role"] = role.as_str().into();
+            value["related"].push(related).ok();
+        }
+        value
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: [{}] {}", self.node.get_location(), self.detector, self.message)
+    }
+}
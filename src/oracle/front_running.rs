@@ -0,0 +1,185 @@
+use std::collections::{ BTreeMap, HashSet };
+use crate::dfg::Network;
+use crate::core::{ Utils, Walker };
+use crate::oracle::{
+    CallKind,
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Detector of transaction ordering dependence
+///
+/// A public function setting a state variable with `=`, e.g `approve` or `setReward`, races with
+/// another public function reading it to compute an amount: the value of an ether transfer, an
+/// integer argument of an external call, a token balance or a bound of an amount in a condition
+/// such as `require(value <= allowed[from][msg.sender])`. Addresses are not amounts and a
+/// comparison with `now` or the block number is a deadline. Whoever sees the first transaction
+/// pending can send the second one before it
+pub struct FrontRunningDetector;
+
+impl FrontRunningDetector {
+    /// State variables set with `=` by a function and the assignments
+    fn find_setters<'a>(context: &Context, function: &Walker<'a>) -> BTreeMap<u32, Walker<'a>> {
+        let dict = context.get_dict();
+        let mut setters = BTreeMap::new();
+        let ig = |_: &Walker, _: &Vec<Walker>| false;
+        let fi = |walker: &Walker, _: &Vec<Walker>| {
+            walker.node.name == "Assignment" && walker.node.attributes["operator"].as_str() == Some("=")
+        };
+        for assignment in function.walk(false, ig, fi).unwrap_or_default() {
+            let state_id = assignment.direct_childs(|_| true).unwrap_or_default().first()
+                .and_then(|left| Utils::find_root_walker(left, dict).ok())
+                .and_then(|root| root.node.attributes["referencedDeclaration"].as_u32())
+                .filter(|reference| context.get_states().contains(reference));
+            if let Some(state_id) = state_id {
+                setters.entry(state_id).or_insert(assignment);
+            }
+        }
+        setters
+    }
+
+    /// Whether an expression is an integer, the only kind of value an amount has
+    fn is_integer(walker: &Walker) -> bool {
+        let kind = Utils::normalize_kind(walker);
+        kind.starts_with("uint") || kind.starts_with("int")
+    }
+
+    /// Amounts computed by a function, the vertex computing them and what they are
+    fn find_amounts<'a>(context: &'a Context, function: &Walker) -> Vec<(u32, Walker<'a>, &'static str)> {
+        let dict = context.get_dict();
+        let mut amounts = vec![];
+        let mut visited = HashSet::new();
+        for execution_path in context.get_execution_paths(function.node.id) {
+            for vertex_id in execution_path {
+                if !visited.insert(*vertex_id) {
+                    continue;
+                }
+                let walker = match dict.walker_at(*vertex_id) {
+                    Some(walker) => walker,
+                    None => continue,
+                };
+                if let Some(call_site) = CallSite::new(walker) {
+                    if let Some(value) = call_site.get_value().and_then(|value| dict.walker_at(value)) {
+                        amounts.push((*vertex_id, value.clone(), "ether transfer"));
+                    }
+                    if call_site.get_kind() == CallKind::External {
+                        let arguments = call_site.get_arguments().iter()
+                            .filter_map(|id| dict.walker_at(*id))
+                            .filter(|argument| FrontRunningDetector::is_integer(argument));
+                        for argument in arguments {
+                            amounts.push((*vertex_id, argument.clone(), "token amount"));
+                        }
+                    }
+                }
+                if let Some(condition) = context.get_condition(*vertex_id) {
+                    let ig = |_: &Walker, _: &Vec<Walker>| false;
+                    let fi = |walker: &Walker, _: &Vec<Walker>| {
+                        let operator = walker.node.attributes["operator"].as_str().unwrap_or("");
+                        walker.node.name == "BinaryOperation" && matches!(operator, "<" | ">" | "<=" | ">=")
+                    };
+                    for comparison in condition.walk(false, ig, fi).unwrap_or_default() {
+                        let operands = comparison.direct_childs(|_| true).unwrap_or_default();
+                        let is_deadline = operands.iter()
+                            .any(|operand| matches!(operand.node.source, "now" | "block.timestamp" | "block.number"));
+                        if is_deadline {
+                            continue;
+                        }
+                        for operand in operands.into_iter().filter(FrontRunningDetector::is_integer) {
+                            amounts.push((condition.node.id, operand, "token amount"));
+                        }
+                    }
+                }
+                let ig = |_: &Walker, _: &Vec<Walker>| false;
+                let fi = |walker: &Walker, _: &Vec<Walker>| walker.node.name == "Assignment";
+                for assignment in walker.walk(true, ig, fi).unwrap_or_default() {
+                    let children = assignment.direct_childs(|_| true).unwrap_or_default();
+                    let is_balance = children.first()
+                        .and_then(|left| Utils::find_root_walker(left, dict).ok())
+                        .and_then(|root| root.node.attributes["referencedDeclaration"].as_u32())
+                        .filter(|reference| context.get_states().contains(reference))
+                        .and_then(|reference| dict.walker_at(reference))
+                        .map(|state| Utils::normalize_kind(state).starts_with("mapping"))
+                        .unwrap_or(false);
+                    if let (true, Some(right)) = (is_balance, children.get(1)) {
+                        amounts.push((*vertex_id, right.clone(), "token amount"));
+                    }
+                }
+            }
+        }
+        amounts
+    }
+}
+
+impl Detector for FrontRunningDetector {
+    fn id(&self) -> &str {
+        "front-running"
+    }
+
+    fn description(&self) -> &str {
+        "Amounts depend on the order of transactions"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    /// The amount is the primary node, the assignment setting the state variable is `write`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let functions = context.get_functions().into_iter()
+            .filter(|function| context.is_public(function) && !context.is_constructor(function))
+            .collect::<Vec<&Walker>>();
+        let setters = functions.iter()
+            .map(|function| (function.node.id, FrontRunningDetector::find_setters(&context, function)))
+            .collect::<Vec<(u32, BTreeMap<u32, Walker>)>>();
+        // (reader, state, writer) => amount, assignment and what the amount is
+        let mut races: BTreeMap<(u32, u32, u32), (u32, u32, &str)> = BTreeMap::new();
+        for reader in functions.iter() {
+            for (vertex_id, amount, usage) in FrontRunningDetector::find_amounts(&context, reader) {
+                let states = context.get_dependencies(vertex_id, &amount).iter()
+                    .filter_map(|(variable, _)| context.get_state(variable))
+                    .collect::<HashSet<u32>>();
+                for (writer_id, writes) in setters.iter() {
+                    if *writer_id == reader.node.id {
+                        continue;
+                    }
+                    for (state_id, assignment) in writes.iter() {
+                        if states.contains(state_id) {
+                            let key = (reader.node.id, *state_id, *writer_id);
+                            races.entry(key).or_insert((amount.node.id, assignment.node.id, usage));
+                        }
+                    }
+                }
+            }
+        }
+        let get_name = |function_id: u32| {
+            dict.walker_at(function_id)
+                .and_then(|function| function.node.attributes["name"].as_str())
+                .filter(|name| !name.is_empty())
+                .unwrap_or("fallback")
+        };
+        let mut findings = vec![];
+        for ((reader_id, state_id, writer_id), (amount_id, assignment_id, usage)) in races {
+            let (amount, assignment) = match (dict.walker_at(amount_id), dict.walker_at(assignment_id)) {
+                (Some(amount), Some(assignment)) => (amount, assignment),
+                _ => continue,
+            };
+            let message = format!(
+                "{} in `{}` depends on `{}` which `{}` sets, the order of transactions decides it",
+                usage,
+                get_name(reader_id),
+                context.get_state_name(state_id),
+                get_name(writer_id),
+            );
+            let finding = Finding::new(self.id(), self.severity(), Confidence::Medium, amount, &message)
+                .with_related("write", assignment);
+            findings.push(finding);
+        }
+        findings
+    }
+}
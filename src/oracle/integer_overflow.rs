@@ -0,0 +1,315 @@
+use std::collections::{ BTreeMap, HashMap, HashSet };
+use crate::dfg::Network;
+use crate::loader::Version;
+use crate::core::{
+    Action,
+    Member,
+    Variable,
+    Walker,
+};
+use crate::oracle::{
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// An arithmetic operation which is not guarded on some execution path
+struct Overflow {
+    /// Input of the caller which flows into an operand, e.g `msg.value` and the vertex reading it
+    controlled: Option<(String, u32)>,
+}
+
+/// Variables of the operands of an arithmetic operation and of its result
+struct Operands {
+    left: HashSet<Vec<Member>>,
+    right: HashSet<Vec<Member>>,
+    result: HashSet<Vec<Member>>,
+    subtraction: bool,
+}
+
+impl Operands {
+    /// Whether `greater >= lesser` rules out an overflow, checked before the operation or after it
+    ///
+    /// `a - b` needs `a >= b` before it or `a >= c` after `c = a - b`, `a + b` and `a * b` need
+    /// the operation itself to bound an operand, `a + b >= a` before it or `c >= a` after it
+    fn is_bounded(&self, greater: &HashSet<Vec<Member>>, lesser: &HashSet<Vec<Member>>, after: bool) -> bool {
+        let touches = |side: &HashSet<Vec<Member>>, operand: &HashSet<Vec<Member>>| !side.is_disjoint(operand);
+        let bounds_operand = touches(lesser, &self.left) || touches(lesser, &self.right);
+        match (self.subtraction, after) {
+            (true, false) => touches(greater, &self.left) && touches(lesser, &self.right),
+            // `a -= b` overwrites `a`, nothing is left to compare the result with
+            (true, true) => self.result != self.left && touches(greater, &self.left) && touches(lesser, &self.result),
+            (false, false) => touches(greater, &self.left) && touches(greater, &self.right) && bounds_operand,
+            (false, true) => touches(greater, &self.result) && bounds_operand,
+        }
+    }
+}
+
+/// Detector of integer overflows and underflows
+///
+/// Arithmetic does not revert before 0.8.0. `+`, `-`, `*`, `+=`, `-=` and `*=` of integers are
+/// reported unless every execution path bounds them with a comparison in the right direction: a
+/// condition before the operation, in a modifier of the function or in an if holding the
+/// operation, e.g `require(balance >= amount)` before `balance -= amount`, or a `require` or an
+/// `assert` of the result right after it, e.g `assert(c >= a)` after `c = a + b`. SafeMath calls
+/// such as `a.add(b)` are not operations and library functions are skipped
+pub struct IntegerOverflowDetector;
+
+impl IntegerOverflowDetector {
+    /// `uint`, `int256`, ... but not literals
+    fn is_integer(kind: &str) -> bool {
+        let bits = kind.strip_prefix("uint").or_else(|| kind.strip_prefix("int"));
+        matches!(bits, Some(bits) if bits.chars().all(|c| c.is_ascii_digit()))
+    }
+
+    fn is_arithmetic(walker: &Walker) -> bool {
+        let operator = walker.node.attributes["operator"].as_str().unwrap_or("");
+        let kind = walker.node.attributes["type"].as_str().unwrap_or("");
+        let arithmetic = match walker.node.name {
+            "BinaryOperation" => matches!(operator, "+" | "-" | "*"),
+            "Assignment" => matches!(operator, "+=" | "-=" | "*="),
+            _ => false,
+        };
+        arithmetic && IntegerOverflowDetector::is_integer(kind)
+    }
+
+    /// Variables of an expression, a parameter of a modifier is replaced with the argument of the
+    /// invocation in `arguments`
+    fn get_members(
+        context: &Context,
+        expression: &Walker,
+        arguments: &HashMap<u32, HashSet<Vec<Member>>>,
+    ) -> HashSet<Vec<Member>> {
+        let mut members = HashSet::new();
+        for variable in Variable::parse(expression, context.get_dict()).unwrap_or_default() {
+            let variable_members = variable.get_members();
+            match variable_members.first() {
+                Some(Member::Reference(id)) if arguments.contains_key(id) => {
+                    for argument in arguments[id].iter() {
+                        members.insert(argument.iter().chain(&variable_members[1..]).cloned().collect());
+                    }
+                },
+                _ => {
+                    members.insert(variable_members.clone());
+                },
+            }
+        }
+        members
+    }
+
+    /// Whether a condition having the value `expected` bounds an operation, the condition is
+    /// checked before the operation or after it
+    fn is_guard(
+        context: &Context,
+        condition: &Walker,
+        expected: bool,
+        operands: &Operands,
+        after: bool,
+        arguments: &HashMap<u32, HashSet<Vec<Member>>>,
+    ) -> bool {
+        let operator = condition.node.attributes["operator"].as_str().unwrap_or("");
+        let children = condition.direct_childs(|_| true).unwrap_or_default();
+        let is_guard = |condition: &Walker, expected: bool| {
+            IntegerOverflowDetector::is_guard(context, condition, expected, operands, after, arguments)
+        };
+        match (condition.node.name, operator, children.as_slice()) {
+            ("TupleExpression", _, [expression]) => is_guard(expression, expected),
+            ("UnaryOperation", "!", [expression]) => is_guard(expression, !expected),
+            // Both operands hold if `&&` is true, neither does if `||` is false
+            ("BinaryOperation", "&&", [left, right]) | ("BinaryOperation", "||", [left, right])
+                if expected == (operator == "&&") => is_guard(left, expected) || is_guard(right, expected),
+            // Either operand holds, each of them must bound the operation
+            ("BinaryOperation", "&&", [left, right]) | ("BinaryOperation", "||", [left, right]) => {
+                is_guard(left, expected) && is_guard(right, expected)
+            },
+            ("BinaryOperation", "<", [left, right])
+            | ("BinaryOperation", "<=", [left, right])
+            | ("BinaryOperation", ">", [left, right])
+            | ("BinaryOperation", ">=", [left, right]) => {
+                // `a < b` being false means `a >= b`
+                let (greater, lesser) = match matches!(operator, ">" | ">=") == expected {
+                    true => (left, right),
+                    false => (right, left),
+                };
+                let greater = IntegerOverflowDetector::get_members(context, greater, arguments);
+                let lesser = IntegerOverflowDetector::get_members(context, lesser, arguments);
+                operands.is_bounded(&greater, &lesser, after)
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether a modifier of a function bounds an operation before its placeholder on every
+    /// execution path
+    fn is_guarded_by_modifier(context: &Context, function_id: u32, operands: &Operands) -> bool {
+        context.get_modifier_invocations(function_id).iter().any(|(invocation, modifier)| {
+            let arguments = context.get_parameters(modifier.node.id).iter().cloned()
+                .zip(invocation.direct_childs(|_| true).unwrap_or_default().into_iter().skip(1))
+                .map(|(parameter, argument)| {
+                    (parameter, IntegerOverflowDetector::get_members(context, &argument, &HashMap::new()))
+                })
+                .collect::<HashMap<u32, HashSet<Vec<Member>>>>();
+            let placeholders = context.find_placeholders(modifier.node.id);
+            !placeholders.is_empty() && placeholders.iter().all(|(execution_path, index)| {
+                context.is_reached_if(execution_path, *index, |condition, expected| {
+                    IntegerOverflowDetector::is_guard(context, condition, expected, operands, false, &arguments)
+                })
+            })
+        })
+    }
+
+    /// Find an input of the caller which an operand at `vertex_id` depends on
+    fn find_controlled(
+        context: &Context,
+        network: &Network,
+        vertex_id: u32,
+        operands: &HashSet<Vec<Member>>,
+        parameters: &HashSet<u32>,
+    ) -> Option<(String, u32)> {
+        for action in context.get_actions(vertex_id) {
+            let variable = match action {
+                Action::Use(variable, _) if operands.contains(variable.get_members()) => variable,
+                _ => continue,
+            };
+            for dependent_path in network.traverse((variable.clone(), vertex_id)) {
+                for (variable, dependent_id) in dependent_path {
+                    let controlled = match variable.get_members().first() {
+                        // Addresses are flattened to their members, e.g `msg.sender.balance`
+                        Some(Member::Global(_)) => matches!(variable.get_unflat(), "msg.value" | "msg.sender"),
+                        Some(Member::Reference(id)) => parameters.contains(id),
+                        _ => false,
+                    };
+                    if controlled {
+                        return Some((variable.get_unflat().to_string(), dependent_id));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Detector for IntegerOverflowDetector {
+    fn id(&self) -> &str {
+        "integer-overflow"
+    }
+
+    fn description(&self) -> &str {
+        "Integer arithmetic may overflow or underflow"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    /// The operation is the primary node, the vertex reading an input of the caller is `source`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        if !context.allows_version_below(&Version::new(0, 8, 0)) {
+            return vec![];
+        }
+        let functions = context.get_functions().into_iter()
+            .filter(|function| {
+                function.node.attributes["scope"].as_u32()
+                    .and_then(|scope| dict.walker_at(scope))
+                    .map(|contract| contract.node.attributes["contractKind"].as_str() != Some("library"))
+                    .unwrap_or(true)
+            })
+            .collect::<Vec<&Walker>>();
+        let parameters = functions.iter()
+            .filter(|function| context.is_public(function))
+            .flat_map(|function| context.get_parameters(function.node.id).to_vec())
+            .collect::<HashSet<u32>>();
+        let mut overflows: BTreeMap<u32, Overflow> = BTreeMap::new();
+        for function in functions {
+            let ig = |_: &Walker, _: &Vec<Walker>| false;
+            let fi = |walker: &Walker, _: &Vec<Walker>| IntegerOverflowDetector::is_arithmetic(walker);
+            for operation in function.walk(false, ig, fi).unwrap_or_default() {
+                let operator = operation.node.attributes["operator"].as_str().unwrap_or("");
+                let children = operation.direct_childs(|_| true).unwrap_or_default();
+                let get_operand = |index: usize| {
+                    children.get(index)
+                        .map(|operand| IntegerOverflowDetector::get_members(&context, operand, &HashMap::new()))
+                        .unwrap_or_default()
+                };
+                let mut operands = Operands {
+                    left: get_operand(0),
+                    right: get_operand(1),
+                    result: HashSet::new(),
+                    subtraction: operator.starts_with('-'),
+                };
+                let all_operands = operands.left.union(&operands.right).cloned().collect::<HashSet<Vec<Member>>>();
+                if IntegerOverflowDetector::is_guarded_by_modifier(&context, function.node.id, &operands) {
+                    continue;
+                }
+                let mut guarded = true;
+                let mut vertices = vec![];
+                for execution_path in context.get_execution_paths(function.node.id) {
+                    let index = match context.find_vertex(execution_path, &operation) {
+                        Some(index) => index,
+                        None => continue,
+                    };
+                    let vertex_id = execution_path[index];
+                    operands.result = context.get_actions(vertex_id).iter()
+                        .filter_map(|action| match action {
+                            Action::Kill(variable, _) => Some(variable.get_members().clone()),
+                            Action::Use(_, _) => None,
+                        })
+                        .collect::<HashSet<Vec<Member>>>();
+                    let no_arguments = HashMap::new();
+                    let is_guard = |condition: &Walker, expected: bool, after: bool| {
+                        IntegerOverflowDetector::is_guard(&context, condition, expected, &operands, after, &no_arguments)
+                    };
+                    let checked_before = context.is_reached_if(execution_path, index, |condition, expected| {
+                        is_guard(condition, expected, false)
+                    });
+                    let checked_after = context.find_next_assertion(execution_path, index)
+                        .map(|condition| !operands.result.is_empty() && is_guard(&condition, true, true))
+                        .unwrap_or(false);
+                    guarded &= checked_before || checked_after;
+                    if !vertices.contains(&vertex_id) {
+                        vertices.push(vertex_id);
+                    }
+                }
+                if guarded {
+                    continue;
+                }
+                let controlled = vertices.iter().find_map(|vertex_id| {
+                    IntegerOverflowDetector::find_controlled(&context, network, *vertex_id, &all_operands, &parameters)
+                });
+                overflows.insert(operation.node.id, Overflow { controlled });
+            }
+        }
+        let mut findings = vec![];
+        for (operation_id, overflow) in overflows {
+            let operation = match dict.walker_at(operation_id) {
+                Some(walker) => walker,
+                None => continue,
+            };
+            let operator = operation.node.attributes["operator"].as_str().unwrap_or("");
+            let kind = match operator.starts_with('-') {
+                true => "underflow",
+                false => "overflow",
+            };
+            let finding = match overflow.controlled {
+                Some((input, source_id)) => {
+                    let message = format!("integer {} of `{}`, an operand depends on `{}`", kind, operator, input);
+                    let finding = Finding::new(self.id(), Severity::High, Confidence::Medium, operation, &message);
+                    match dict.walker_at(source_id) {
+                        Some(source) => finding.with_related("source", source),
+                        None => finding,
+                    }
+                },
+                None => {
+                    let message = format!("integer {} of `{}`", kind, operator);
+                    Finding::new(self.id(), Severity::Medium, Confidence::Low, operation, &message)
+                },
+            };
+            findings.push(finding);
+        }
+        findings
+    }
+}
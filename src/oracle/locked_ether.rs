@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use crate::dfg::Network;
+use crate::core::Walker;
+use crate::oracle::{
+    CallKind,
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Detector of contracts which receive ether but can not send it
+///
+/// A contract is reported if it has a payable function or fallback while no function, inherited
+/// ones included, reaches `transfer`, `send`, a call with value or `selfdestruct`. A
+/// `delegatecall` or a library function which may change the state can still move the ether, the
+/// confidence is lower if one of them is reachable
+pub struct LockedEtherDetector;
+
+impl LockedEtherDetector {
+    /// A call of a library function which is not `view` or `pure`, e.g `Wallet.withdraw(..)`
+    fn is_library_call(context: &Context, call: &Walker) -> bool {
+        let dict = context.get_dict();
+        let function = call.direct_childs(|_| true).unwrap_or_default().first()
+            .and_then(|callee| callee.node.attributes["referencedDeclaration"].as_u32())
+            .and_then(|reference| dict.walker_at(reference))
+            .filter(|function| function.node.name == "FunctionDefinition");
+        let function = match function {
+            Some(function) => function,
+            None => return false,
+        };
+        let is_library = function.node.attributes["scope"].as_u32()
+            .and_then(|scope| dict.walker_at(scope))
+            .map(|contract| contract.node.attributes["contractKind"].as_str() == Some("library"))
+            .unwrap_or(false);
+        is_library && !context.is_read_only(function)
+    }
+}
+
+impl Detector for LockedEtherDetector {
+    fn id(&self) -> &str {
+        "locked-ether"
+    }
+
+    fn description(&self) -> &str {
+        "Contract receives ether but can not send it"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    /// The contract is the primary node, payable functions are `payable` and reachable
+    /// delegatecalls and library calls are `escape`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let contract = match dict.walker_at(network.get_contract_id()) {
+            Some(contract) => contract,
+            None => return vec![],
+        };
+        let is_contract = contract.node.attributes["contractKind"].as_str().unwrap_or("contract") == "contract";
+        let is_implemented = contract.node.attributes["fullyImplemented"].as_bool().unwrap_or(true);
+        if !is_contract || !is_implemented {
+            return vec![];
+        }
+        let functions = context.get_functions();
+        let payables = functions.iter()
+            .filter(|function| context.is_payable(function))
+            .collect::<Vec<&&Walker>>();
+        if payables.is_empty() {
+            return vec![];
+        }
+        let mut scopes = vec![];
+        for function in functions.iter() {
+            scopes.push(function.node.id);
+            scopes.extend(context.get_modifiers(function.node.id).iter().map(|modifier| modifier.node.id));
+        }
+        let mut escapes: BTreeMap<u32, &Walker> = BTreeMap::new();
+        for scope_id in scopes {
+            for execution_path in context.get_execution_paths(scope_id) {
+                for vertex_id in execution_path {
+                    let call = match dict.walker_at(*vertex_id) {
+                        Some(walker) => walker,
+                        None => continue,
+                    };
+                    let call_site = match CallSite::new(call) {
+                        Some(call_site) => call_site,
+                        None => continue,
+                    };
+                    if call_site.sends_value() {
+                        return vec![];
+                    }
+                    let is_delegatecall = call_site.get_kind() == CallKind::LowLevel
+                        && matches!(call_site.get_member(), "delegatecall" | "callcode");
+                    if is_delegatecall || LockedEtherDetector::is_library_call(&context, call) {
+                        escapes.insert(*vertex_id, call);
+                    }
+                }
+            }
+        }
+        let name = contract.node.attributes["name"].as_str().unwrap_or("");
+        let (confidence, message) = match escapes.is_empty() {
+            true => (Confidence::High, format!("`{}` receives ether but never sends it", name)),
+            false => (
+                Confidence::Low,
+                format!("`{}` receives ether but only a delegatecall or a library call may send it", name),
+            ),
+        };
+        let mut finding = Finding::new(self.id(), self.severity(), confidence, contract, &message);
+        for payable in payables {
+            finding = finding.with_related("payable", payable);
+        }
+        for (_, escape) in escapes {
+            finding = finding.with_related("escape", escape);
+        }
+        vec![finding]
+    }
+}
@@ -1,5 +1,41 @@
 mod oracle;
 mod unsafe_condition;
+mod finding;
+mod detector;
+mod registry;
+mod call;
+mod context;
+mod reentrancy;
+mod integer_overflow;
+mod tx_origin;
+mod unchecked_call;
+mod selfdestruct;
+mod delegatecall;
+mod costly_loop;
+mod uninitialized_storage;
+mod weak_randomness;
+mod arbitrary_send;
+mod locked_ether;
+mod access_control;
+mod front_running;
 
 pub use oracle::*;
 pub use unsafe_condition::*;
+pub use finding::*;
+pub use detector::*;
+pub use registry::*;
+pub use call::*;
+pub use context::*;
+pub use reentrancy::*;
+pub use integer_overflow::*;
+pub use tx_origin::*;
+pub use unchecked_call::*;
+pub use selfdestruct::*;
+pub use delegatecall::*;
+pub use costly_loop::*;
+pub use uninitialized_storage::*;
+pub use weak_randomness::*;
+pub use arbitrary_send::*;
+pub use locked_ether::*;
+pub use access_control::*;
+pub use front_running::*;
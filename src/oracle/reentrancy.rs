@@ -0,0 +1,225 @@
+use std::collections::{ BTreeMap, BTreeSet, HashSet };
+use crate::dfg::Network;
+use crate::core::{ Utils, Walker };
+use crate::oracle::{
+    CallKind,
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Writes of state variables after an external call
+#[derive(Default)]
+struct Reentrancy {
+    sends_value: bool,
+    /// Whether a written variable is also read before the call
+    harmful: bool,
+    writes: BTreeMap<u32, BTreeSet<u32>>,
+}
+
+/// Detector of state variables written after an external call
+///
+/// The callee may call back into the contract before the write and see a stale state. Only
+/// `call` and functions of other contracts are considered, `send` and `transfer` forward too
+/// little gas to change the state. A finding is harmful if the written variable is read before
+/// the call, e.g a balance which is checked, sent and reset, otherwise it is benign
+pub struct ReentrancyDetector;
+
+impl ReentrancyDetector {
+    /// Bool state variable of an expression, e.g `locked`
+    fn get_flag(context: &Context, walker: &Walker) -> Option<u32> {
+        let dict = context.get_dict();
+        walker.node.attributes["referencedDeclaration"].as_u32()
+            .filter(|reference| context.get_states().contains(reference))
+            .filter(|reference| {
+                dict.walker_at(*reference)
+                    .map(|state| Utils::normalize_kind(state) == "bool")
+                    .unwrap_or(false)
+            })
+    }
+
+    /// Bool state variable which a `require` or an `assert` at a vertex requires to be false,
+    /// e.g `locked` of `require(!locked)` or `require(locked == false)`
+    fn find_guard(context: &Context, vertex_id: u32) -> Option<u32> {
+        let dict = context.get_dict();
+        let call_site = dict.walker_at(vertex_id).and_then(CallSite::new)?;
+        if call_site.get_kind() != CallKind::Internal || !matches!(call_site.get_member(), "require" | "assert") {
+            return None;
+        }
+        let condition = context.get_condition(vertex_id)?;
+        let operator = condition.node.attributes["operator"].as_str().unwrap_or("");
+        let operands = condition.direct_childs(|_| true).unwrap_or_default();
+        match (condition.node.name, operator, operands.as_slice()) {
+            ("UnaryOperation", "!", [operand]) => ReentrancyDetector::get_flag(context, operand),
+            ("BinaryOperation", "==", [left, right]) | ("BinaryOperation", "!=", [left, right]) => {
+                let expected = match operator == "==" {
+                    true => "false",
+                    false => "true",
+                };
+                let is_expected = |walker: &Walker| {
+                    walker.node.name == "Literal" && walker.node.attributes["value"].as_str() == Some(expected)
+                };
+                match (is_expected(left), is_expected(right)) {
+                    (false, true) => ReentrancyDetector::get_flag(context, left),
+                    (true, false) => ReentrancyDetector::get_flag(context, right),
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Bool state variables set to `value` at a vertex, e.g `locked` of `locked = true`
+    fn find_flag_writes(context: &Context, vertex_id: u32, value: &str) -> HashSet<u32> {
+        let mut flags = HashSet::new();
+        let walker = match context.get_dict().walker_at(vertex_id) {
+            Some(walker) => walker,
+            None => return flags,
+        };
+        let ig = |walker: &Walker, _: &Vec<Walker>| walker.node.name == "FunctionCall";
+        let fi = |walker: &Walker, _: &Vec<Walker>| {
+            walker.node.name == "Assignment" && walker.node.attributes["operator"].as_str() == Some("=")
+        };
+        for assignment in walker.walk(true, ig, fi).unwrap_or_default() {
+            if let [left, right] = assignment.direct_childs(|_| true).unwrap_or_default().as_slice() {
+                if right.node.name == "Literal" && right.node.attributes["value"].as_str() == Some(value) {
+                    flags.extend(ReentrancyDetector::get_flag(context, left));
+                }
+            }
+        }
+        flags
+    }
+
+    /// Whether a modifier is a mutex: on every execution path reaching its placeholder a bool
+    /// state variable is required to be false, set to true before the placeholder and reset to
+    /// false after it
+    fn is_mutex(context: &Context, modifier: &Walker) -> bool {
+        let dict = context.get_dict();
+        let mut mutexes: Option<HashSet<u32>> = None;
+        for execution_path in context.get_execution_paths(modifier.node.id) {
+            let placeholder = execution_path.iter().position(|vertex_id| {
+                dict.walker_at(*vertex_id)
+                    .map(|walker| walker.node.name == "PlaceholderStatement")
+                    .unwrap_or(false)
+            });
+            let placeholder = match placeholder {
+                Some(placeholder) => placeholder,
+                None => continue,
+            };
+            let mut guarded = HashSet::new();
+            let mut locked = HashSet::new();
+            for vertex_id in execution_path[..placeholder].iter() {
+                for flag in ReentrancyDetector::find_flag_writes(context, *vertex_id, "true") {
+                    if guarded.contains(&flag) {
+                        locked.insert(flag);
+                    }
+                }
+                guarded.extend(ReentrancyDetector::find_guard(context, *vertex_id));
+            }
+            let unlocked = execution_path[placeholder + 1..].iter()
+                .flat_map(|vertex_id| ReentrancyDetector::find_flag_writes(context, *vertex_id, "false"))
+                .collect::<HashSet<u32>>();
+            let flags = locked.intersection(&unlocked).cloned().collect::<HashSet<u32>>();
+            mutexes = match mutexes {
+                Some(mutexes) => Some(mutexes.intersection(&flags).cloned().collect()),
+                None => Some(flags),
+            };
+        }
+        mutexes.map(|mutexes| !mutexes.is_empty()).unwrap_or(false)
+    }
+
+    /// Functions protected by a mutex modifier, e.g `nonReentrant`, are skipped
+    fn has_mutex(context: &Context, function_id: u32) -> bool {
+        context.get_modifiers(function_id).iter().any(|modifier| ReentrancyDetector::is_mutex(context, modifier))
+    }
+}
+
+impl Detector for ReentrancyDetector {
+    fn id(&self) -> &str {
+        "reentrancy"
+    }
+
+    fn description(&self) -> &str {
+        "State variables are written after an external call"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    /// The external call is the primary node, the writes after it are `write`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let mut reentrancies: BTreeMap<u32, Reentrancy> = BTreeMap::new();
+        for function in context.get_functions() {
+            let function_id = function.node.id;
+            if context.is_constructor(function)
+                || context.is_read_only(function)
+                || ReentrancyDetector::has_mutex(&context, function_id) {
+                continue;
+            }
+            for execution_path in context.get_execution_paths(function_id) {
+                for (index, vertex_id) in execution_path.iter().enumerate() {
+                    let call_site = match dict.walker_at(*vertex_id).and_then(CallSite::new) {
+                        Some(call_site) => call_site,
+                        None => continue,
+                    };
+                    let reentrant = match call_site.get_kind() {
+                        CallKind::LowLevel => call_site.get_member() == "call",
+                        CallKind::External => true,
+                        _ => false,
+                    };
+                    if !reentrant {
+                        continue;
+                    }
+                    let reads = execution_path[..=index].iter()
+                        .flat_map(|vertex_id| context.get_state_reads(*vertex_id))
+                        .collect::<HashSet<u32>>();
+                    for write_id in execution_path[index + 1..].iter() {
+                        for state_id in context.get_state_writes(*write_id) {
+                            let reentrancy = reentrancies.entry(*vertex_id).or_default();
+                            reentrancy.sends_value = call_site.sends_value();
+                            reentrancy.harmful |= reads.contains(&state_id);
+                            reentrancy.writes.entry(*write_id).or_default().insert(state_id);
+                        }
+                    }
+                }
+            }
+        }
+        let mut findings = vec![];
+        for (call_id, reentrancy) in reentrancies {
+            let call_walker = match dict.walker_at(call_id) {
+                Some(walker) => walker,
+                None => continue,
+            };
+            let states = reentrancy.writes.values()
+                .flatten()
+                .collect::<BTreeSet<&u32>>()
+                .into_iter()
+                .map(|state_id| format!("`{}`", context.get_state_name(*state_id)))
+                .collect::<Vec<String>>();
+            let (severity, call) = match (reentrancy.sends_value, reentrancy.harmful) {
+                (true, true) => (Severity::High, "external call sending ether"),
+                (false, true) => (Severity::Medium, "external call"),
+                (true, false) => (Severity::Low, "external call sending ether"),
+                (false, false) => (Severity::Low, "external call"),
+            };
+            let message = match reentrancy.harmful {
+                true => format!("{} is followed by a write to {} which is read before the call", call, states.join(", ")),
+                false => format!("{} is followed by a write to {}, it is benign as the variable is not read before the call", call, states.join(", ")),
+            };
+            let mut finding = Finding::new(self.id(), severity, Confidence::Medium, call_walker, &message);
+            for write_id in reentrancy.writes.keys() {
+                if let Some(write_walker) = dict.walker_at(*write_id) {
+                    finding = finding.with_related("write", write_walker);
+                }
+            }
+            findings.push(finding);
+        }
+        findings
+    }
+}
@@ -0,0 +1,115 @@
+use crate::dfg::Network;
+use crate::oracle::{
+    Detector,
+    Finding,
+    UnsafeSendingConditionDetector,
+    ReentrancyDetector,
+    IntegerOverflowDetector,
+    TxOriginDetector,
+    UncheckedCallDetector,
+    SelfdestructDetector,
+    DelegatecallDetector,
+    CostlyLoopDetector,
+    UninitializedStorageDetector,
+    WeakRandomnessDetector,
+    ArbitrarySendDetector,
+    LockedEtherDetector,
+    AccessControlDetector,
+    FrontRunningDetector,
+};
+
+/// A collection of detectors which can be enabled or disabled by id
+#[derive(Default)]
+pub struct Registry {
+    detectors: Vec<(Box<dyn Detector>, bool)>,
+}
+
+impl Registry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// Create a registry of all built-in detectors, they are enabled
+    pub fn with_builtins() -> Self {
+        let mut registry = Registry::new();
+        registry.register(Box::new(UnsafeSendingConditionDetector));
+        registry.register(Box::new(ReentrancyDetector));
+        registry.register(Box::new(IntegerOverflowDetector));
+        registry.register(Box::new(TxOriginDetector));
+        registry.register(Box::new(UncheckedCallDetector));
+        registry.register(Box::new(SelfdestructDetector));
+        registry.register(Box::new(DelegatecallDetector));
+        registry.register(Box::new(CostlyLoopDetector));
+        registry.register(Box::new(UninitializedStorageDetector));
+        registry.register(Box::new(WeakRandomnessDetector));
+        registry.register(Box::new(ArbitrarySendDetector));
+        registry.register(Box::new(LockedEtherDetector));
+        registry.register(Box::new(AccessControlDetector));
+        registry.register(Box::new(FrontRunningDetector));
+        registry
+    }
+
+    /// Add an enabled detector, a detector having the same id is replaced
+    pub fn register(&mut self, detector: Box<dyn Detector>) {
+        match self.detectors.iter_mut().find(|(registered, _)| registered.id() == detector.id()) {
+            Some(entry) => *entry = (detector, true),
+            None => self.detectors.push((detector, true)),
+        }
+    }
+
+    /// All detectors in registered order
+    pub fn list(&self) -> Vec<&dyn Detector> {
+        self.detectors.iter().map(|(detector, _)| detector.as_ref()).collect()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn Detector> {
+        self.detectors.iter()
+            .find(|(detector, _)| detector.id() == id)
+            .map(|(detector, _)| detector.as_ref())
+    }
+
+    /// Enable a detector, return false if no detector has `id`
+    pub fn enable(&mut self, id: &str) -> bool {
+        self.set_enabled(id, true)
+    }
+
+    /// Disable a detector, return false if no detector has `id`
+    pub fn disable(&mut self, id: &str) -> bool {
+        self.set_enabled(id, false)
+    }
+
+    pub fn disable_all(&mut self) {
+        for (_, enabled) in self.detectors.iter_mut() {
+            *enabled = false;
+        }
+    }
+
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.detectors.iter().any(|(detector, enabled)| detector.id() == id && *enabled)
+    }
+
+    fn set_enabled(&mut self, id: &str, value: bool) -> bool {
+        match self.detectors.iter_mut().find(|(detector, _)| detector.id() == id) {
+            Some((_, enabled)) => {
+                *enabled = value;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Run enabled detectors on `network`, findings are sorted by location
+    pub fn run(&self, network: &Network) -> Vec<Finding> {
+        let mut findings = self.detectors.iter()
+            .filter(|(_, enabled)| *enabled)
+            .flat_map(|(detector, _)| detector.check(network))
+            .collect::<Vec<Finding>>();
+        findings.sort_by(|a, b| {
+            let a_key = (a.get_node().get_location(), a.get_detector());
+            let b_key = (b.get_node().get_location(), b.get_detector());
+            a_key.cmp(&b_key)
+        });
+        findings
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::BTreeSet;
+use crate::dfg::Network;
+use crate::oracle::{
+    CallKind,
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Detector of `selfdestruct` and `suicide` which anyone can call
+///
+/// A public function destroying the contract must only reach the call if `msg.sender` equals a
+/// state variable, e.g after `require(msg.sender == owner)` or inside `if (admins[msg.sender])`,
+/// in its body or in a modifier
+pub struct SelfdestructDetector;
+
+impl Detector for SelfdestructDetector {
+    fn id(&self) -> &str {
+        "unprotected-selfdestruct"
+    }
+
+    fn description(&self) -> &str {
+        "Anyone can destroy the contract"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    /// The selfdestruct call is the primary node
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let mut calls = BTreeSet::new();
+        for function in context.get_functions() {
+            let function_id = function.node.id;
+            if !context.is_public(function) || context.is_constructor(function) {
+                continue;
+            }
+            for execution_path in context.get_execution_paths(function_id) {
+                for vertex_id in execution_path {
+                    let is_selfdestruct = dict.walker_at(*vertex_id)
+                        .and_then(CallSite::new)
+                        .map(|call_site| call_site.get_kind() == CallKind::Selfdestruct)
+                        .unwrap_or(false);
+                    if is_selfdestruct && !context.is_sender_checked(function_id, *vertex_id) {
+                        calls.insert(*vertex_id);
+                    }
+                }
+            }
+        }
+        let mut findings = vec![];
+        for call_id in calls {
+            if let Some(call) = dict.walker_at(call_id) {
+                let member = CallSite::new(call)
+                    .map(|call_site| call_site.get_member().to_string())
+                    .unwrap_or_default();
+                let message = format!("`{}` is reachable without checking `msg.sender`", member);
+                findings.push(Finding::new(self.id(), self.severity(), Confidence::Medium, call, &message));
+            }
+        }
+        findings
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::{ BTreeMap, HashSet };
+use crate::dfg::Network;
+use crate::core::{
+    Action,
+    Member,
+    Variable,
+    Walker,
+};
+use crate::oracle::{
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Detector of authorization by `tx.origin`
+///
+/// A condition depending on `tx.origin` lets a contract called by the owner act as the owner. The
+/// condition is reported if it guards a write of a state variable or a transfer of ether, either
+/// in its function or in the functions invoking its modifier. `tx.origin == msg.sender` only
+/// rejects contracts and is not reported
+pub struct TxOriginDetector;
+
+impl TxOriginDetector {
+    /// `tx.origin == msg.sender` or `tx.origin != msg.sender`
+    fn is_benign(walker: &Walker) -> bool {
+        let operator = walker.node.attributes["operator"].as_str().unwrap_or("");
+        if walker.node.name != "BinaryOperation" || !matches!(operator, "==" | "!=") {
+            return false;
+        }
+        let mut operands = walker.direct_childs(|_| true).unwrap_or_default().iter()
+            .map(|operand| operand.node.source.trim())
+            .collect::<Vec<&str>>();
+        operands.sort_unstable();
+        operands == vec!["msg.sender", "tx.origin"]
+    }
+
+    /// Find the vertex reading `tx.origin` which a condition depends on
+    fn find_origin(context: &Context, network: &Network, vertex_id: u32, condition: &Walker) -> Option<u32> {
+        let dict = context.get_dict();
+        let ig = |walker: &Walker, _: &Vec<Walker>| TxOriginDetector::is_benign(walker);
+        let fi = |walker: &Walker, _: &Vec<Walker>| {
+            matches!(walker.node.name, "MemberAccess" | "Identifier" | "IndexAccess" | "FunctionCall")
+                && !TxOriginDetector::is_benign(walker)
+        };
+        let members = condition.walk(true, ig, fi).unwrap_or_default().iter()
+            .flat_map(|walker| Variable::parse(walker, dict).unwrap_or_default())
+            .map(|variable| variable.get_members().clone())
+            .collect::<HashSet<Vec<Member>>>();
+        let mut sources = vec![];
+        for id in [vertex_id, condition.node.id].iter() {
+            for action in context.get_actions(*id) {
+                if let Action::Use(variable, _) = action {
+                    if members.contains(variable.get_members()) {
+                        sources.push((variable.clone(), *id));
+                    }
+                }
+            }
+        }
+        for source in sources {
+            for dependent_path in network.traverse(source) {
+                for (variable, dependent_id) in dependent_path {
+                    // Addresses are flattened to their members, e.g `tx.origin.balance`
+                    if variable.get_unflat() == "tx.origin" {
+                        return Some(dependent_id);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether an execution path writes a state variable or sends ether, the first flag is for
+    /// ether
+    fn find_effects(context: &Context, vertices: &[u32]) -> (bool, bool) {
+        let dict = context.get_dict();
+        let sends_value = vertices.iter()
+            .filter_map(|vertex_id| dict.walker_at(*vertex_id).and_then(CallSite::new))
+            .any(|call_site| call_site.sends_value());
+        let writes = vertices.iter().any(|vertex_id| !context.get_state_writes(*vertex_id).is_empty());
+        (sends_value, writes)
+    }
+}
+
+impl Detector for TxOriginDetector {
+    fn id(&self) -> &str {
+        "tx-origin"
+    }
+
+    fn description(&self) -> &str {
+        "Authorization depends on tx.origin"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    /// The condition is the primary node, the vertex reading `tx.origin` is `source`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let functions = context.get_functions().into_iter()
+            .filter(|function| !context.is_constructor(function))
+            .collect::<Vec<&Walker>>();
+        let mut scopes = functions.iter()
+            .map(|function| function.node.id)
+            .collect::<Vec<u32>>();
+        for function in functions.iter() {
+            for modifier in context.get_modifiers(function.node.id) {
+                if !scopes.contains(&modifier.node.id) {
+                    scopes.push(modifier.node.id);
+                }
+            }
+        }
+        // Condition => (origin, sends ether, writes states)
+        let mut guards: BTreeMap<u32, (u32, bool, bool)> = BTreeMap::new();
+        for scope_id in scopes {
+            // Effects of the functions invoking a modifier
+            let mut invoker_effects = (false, false);
+            for function in functions.iter() {
+                let invokes = context.get_modifiers(function.node.id).iter()
+                    .any(|modifier| modifier.node.id == scope_id);
+                if !invokes {
+                    continue;
+                }
+                for execution_path in context.get_execution_paths(function.node.id) {
+                    let (sends_value, writes) = TxOriginDetector::find_effects(&context, execution_path);
+                    invoker_effects = (invoker_effects.0 || sends_value, invoker_effects.1 || writes);
+                }
+            }
+            for execution_path in context.get_execution_paths(scope_id) {
+                for (index, vertex_id) in execution_path.iter().enumerate() {
+                    let condition = match context.get_condition(*vertex_id) {
+                        Some(condition) => condition,
+                        None => continue,
+                    };
+                    let origin_id = match TxOriginDetector::find_origin(&context, network, *vertex_id, &condition) {
+                        Some(origin_id) => origin_id,
+                        None => continue,
+                    };
+                    let (sends_value, writes) = TxOriginDetector::find_effects(&context, &execution_path[index + 1..]);
+                    let guard = guards.entry(*vertex_id).or_insert((origin_id, false, false));
+                    guard.1 |= sends_value || invoker_effects.0;
+                    guard.2 |= writes || invoker_effects.1;
+                }
+            }
+        }
+        let mut findings = vec![];
+        for (condition_id, (origin_id, sends_value, writes)) in guards {
+            let condition = match dict.walker_at(condition_id) {
+                Some(walker) => walker,
+                None => continue,
+            };
+            let (severity, message) = match (sends_value, writes) {
+                (true, _) => (Severity::High, "condition depending on `tx.origin` guards a transfer of ether"),
+                (false, true) => (Severity::Medium, "condition depending on `tx.origin` guards a write of state variables"),
+                (false, false) => continue,
+            };
+            let mut finding = Finding::new(self.id(), severity, Confidence::Medium, condition, message);
+            // `tx.origin` read outside of the condition, e.g by a local variable
+            if let Some(origin) = dict.walker_at(origin_id) {
+                let range = condition.node.get_range();
+                let origin_range = origin.node.get_range();
+                if origin_range.start < range.start || range.end < origin_range.end {
+                    finding = finding.with_related("source", origin);
+                }
+            }
+            findings.push(finding);
+        }
+        findings
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::{ BTreeMap, HashSet };
+use crate::dfg::Network;
+use crate::core::{ Action, Walker };
+use crate::oracle::{
+    CallKind,
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Detector of low-level calls whose return value is not checked
+///
+/// `send`, `call`, `callcode`, `delegatecall` and `staticcall` return false instead of reverting.
+/// A call is reported if it is an expression statement or if the variable storing its result
+/// never flows into a condition. Results which are returned or passed to another function are
+/// left to the callee
+pub struct UncheckedCallDetector;
+
+impl UncheckedCallDetector {
+    /// Vertices and state variables which conditions of the network depend on, a state variable
+    /// may be checked by another function
+    fn find_checked(context: &Context, network: &Network) -> (HashSet<u32>, HashSet<u32>) {
+        let mut scopes = vec![];
+        for function in context.get_functions() {
+            scopes.push(function.node.id);
+            scopes.extend(context.get_modifiers(function.node.id).iter().map(|modifier| modifier.node.id));
+        }
+        let mut sources = HashSet::new();
+        for scope_id in scopes {
+            for execution_path in context.get_execution_paths(scope_id) {
+                for vertex_id in execution_path {
+                    let condition = match context.get_condition(*vertex_id) {
+                        Some(condition) => condition,
+                        None => continue,
+                    };
+                    for id in [*vertex_id, condition.node.id].iter() {
+                        for action in context.get_actions(*id) {
+                            if let Action::Use(variable, _) = action {
+                                sources.insert((variable.clone(), *id));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let mut vertices = HashSet::new();
+        let mut states = HashSet::new();
+        for source in sources {
+            for (variable, dependent_id) in network.traverse(source).into_iter().flatten() {
+                states.extend(context.get_state(&variable));
+                vertices.insert(dependent_id);
+            }
+        }
+        (vertices, states)
+    }
+
+    /// Innermost statement of an execution path containing a call
+    fn find_statement<'a>(context: &'a Context, execution_path: &[u32], call: &Walker) -> Option<&'a Walker<'a>> {
+        let range = call.node.get_range();
+        execution_path.iter()
+            .filter_map(|vertex_id| context.get_dict().walker_at(*vertex_id))
+            .filter(|vertex| vertex.node.name.ends_with("Statement") || vertex.node.name == "Return")
+            .filter(|vertex| vertex.node.file == call.node.file)
+            .filter(|vertex| {
+                let vertex_range = vertex.node.get_range();
+                vertex_range.start <= range.start && range.end <= vertex_range.end
+            })
+            .min_by_key(|vertex| vertex.node.get_range().len())
+    }
+
+    /// Node which the result of a call is given to
+    fn find_parent<'a>(statement: &Walker<'a>, call: &Walker) -> Option<Walker<'a>> {
+        let call_id = call.node.id;
+        let ig = |_: &Walker, _: &Vec<Walker>| false;
+        let fi = |walker: &Walker, _: &Vec<Walker>| {
+            walker.direct_childs(|_| true).unwrap_or_default().iter().any(|walker| walker.node.id == call_id)
+        };
+        statement.walk(false, ig, fi).unwrap_or_default().into_iter().next()
+    }
+}
+
+impl Detector for UncheckedCallDetector {
+    fn id(&self) -> &str {
+        "unchecked-call"
+    }
+
+    fn description(&self) -> &str {
+        "Return value of a low-level call is not checked"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    /// The call is the primary node, the statement storing its result is `assignment`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let (checked, checked_states) = UncheckedCallDetector::find_checked(&context, network);
+        // Call => statement storing the result, none if the result is discarded
+        let mut unchecked: BTreeMap<u32, Option<u32>> = BTreeMap::new();
+        for function in context.get_functions() {
+            for execution_path in context.get_execution_paths(function.node.id) {
+                for vertex_id in execution_path {
+                    let call = match dict.walker_at(*vertex_id) {
+                        Some(walker) => walker,
+                        None => continue,
+                    };
+                    let is_low_level = CallSite::new(call)
+                        .map(|call_site| matches!(call_site.get_kind(), CallKind::LowLevel | CallKind::Send))
+                        .unwrap_or(false);
+                    if !is_low_level || checked.contains(vertex_id) {
+                        continue;
+                    }
+                    let statement = match UncheckedCallDetector::find_statement(&context, execution_path, call) {
+                        Some(statement) => statement,
+                        None => continue,
+                    };
+                    let parent = match UncheckedCallDetector::find_parent(statement, call) {
+                        Some(parent) => parent,
+                        None => continue,
+                    };
+                    match parent.node.name {
+                        "ExpressionStatement" => {
+                            unchecked.insert(*vertex_id, None);
+                        },
+                        "Assignment" | "VariableDeclarationStatement" | "TupleExpression"
+                            if !checked.contains(&statement.node.id)
+                                && context.get_state_writes(statement.node.id).is_disjoint(&checked_states) => {
+                            unchecked.insert(*vertex_id, Some(statement.node.id));
+                        },
+                        _ => {},
+                    }
+                }
+            }
+        }
+        let mut findings = vec![];
+        for (call_id, assignment_id) in unchecked {
+            let call = match dict.walker_at(call_id) {
+                Some(walker) => walker,
+                None => continue,
+            };
+            let member = CallSite::new(call)
+                .map(|call_site| call_site.get_member().to_string())
+                .unwrap_or_default();
+            let finding = match assignment_id.and_then(|assignment_id| dict.walker_at(assignment_id)) {
+                Some(assignment) => {
+                    let message = format!("return value of `{}` is stored but never checked", member);
+                    Finding::new(self.id(), self.severity(), Confidence::Medium, call, &message)
+                        .with_related("assignment", assignment)
+                },
+                None => {
+                    let message = format!("return value of `{}` is discarded", member);
+                    Finding::new(self.id(), self.severity(), Confidence::High, call, &message)
+                },
+            };
+            findings.push(finding);
+        }
+        findings
+    }
+}
@@ -0,0 +1,174 @@
+use crate::dfg::Network;
+use crate::loader::Version;
+use crate::core::{
+    Dictionary,
+    SmartContractQuery,
+    Utils,
+    Walker,
+};
+use crate::oracle::{
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// Detector of uninitialized local storage pointers
+///
+/// Before 0.5.0 a local struct or array declared without initial value is a pointer to storage
+/// slot 0, writing it overwrites the first state variables of the contract. Aliased variables are
+/// found from the storage layout of `StatesByContractId`
+pub struct UninitializedStorageDetector;
+
+impl UninitializedStorageDetector {
+    /// Whether a type starts a new slot and the next variable starts a new slot
+    fn is_composite(kind: &str) -> bool {
+        kind.starts_with("struct ")
+            || kind.starts_with("mapping")
+            || kind.ends_with(']')
+            || kind == "string"
+            || kind == "bytes"
+    }
+
+    /// Number of bytes of a value type or of the slots of other types
+    fn size_of(dict: &Dictionary, kind: &str) -> usize {
+        if kind.starts_with("mapping") || kind.ends_with("[]") || kind == "string" || kind == "bytes" {
+            return 32;
+        }
+        if let Some(struct_name) = kind.strip_prefix("struct ") {
+            let members = dict.find_walkers(SmartContractQuery::StructByName(struct_name.to_string()))
+                .first()
+                .map(|walker| {
+                    walker.direct_childs(|_| true).unwrap_or_default()
+                        .iter()
+                        .map(Utils::normalize_kind)
+                        .collect::<Vec<String>>()
+                })
+                .unwrap_or_default();
+            let layout = UninitializedStorageDetector::layout(dict, &members);
+            let slots = layout.last().map(|(_, end)| *end).unwrap_or(1);
+            return slots * 32;
+        }
+        if kind.ends_with(']') {
+            let open = kind.rfind('[').unwrap_or(0);
+            let length = kind[open + 1..kind.len() - 1].parse::<usize>().unwrap_or(1);
+            let element = UninitializedStorageDetector::size_of(dict, &kind[..open]);
+            let slots = match element <= 16 && !UninitializedStorageDetector::is_composite(&kind[..open]) {
+                true => length.div_ceil(32 / element),
+                false => length * element.div_ceil(32),
+            };
+            return slots.max(1) * 32;
+        }
+        let bits = |prefix: &str| kind.strip_prefix(prefix).and_then(|bits| bits.parse::<usize>().ok());
+        match kind {
+            "bool" => 1,
+            "address" | "address payable" => 20,
+            _ if kind.starts_with("contract ") => 20,
+            _ if kind.starts_with("enum ") => 1,
+            _ => bits("uint").or_else(|| bits("int")).map(|bits| bits / 8)
+                .or_else(|| bits("bytes"))
+                .unwrap_or(32),
+        }
+    }
+
+    /// Slots `[start, end)` of a sequence of types
+    fn layout(dict: &Dictionary, kinds: &[String]) -> Vec<(usize, usize)> {
+        let mut slots = vec![];
+        let (mut slot, mut offset) = (0, 0);
+        for kind in kinds {
+            let size = UninitializedStorageDetector::size_of(dict, kind);
+            if UninitializedStorageDetector::is_composite(kind) {
+                if offset > 0 {
+                    slot += 1;
+                    offset = 0;
+                }
+                slots.push((slot, slot + size / 32));
+                slot += size / 32;
+            } else {
+                if offset + size > 32 {
+                    slot += 1;
+                    offset = 0;
+                }
+                slots.push((slot, slot + 1));
+                offset += size;
+            }
+        }
+        slots
+    }
+
+    /// Local declarations without initial value, e.g `Data data;`
+    fn find_declarations<'a>(function: &Walker<'a>) -> Vec<Walker<'a>> {
+        let ig = |_: &Walker, _: &Vec<Walker>| false;
+        let fi = |walker: &Walker, _: &Vec<Walker>| walker.node.name == "VariableDeclarationStatement";
+        function.walk(false, ig, fi).unwrap_or_default().into_iter()
+            .map(|statement| statement.direct_childs(|_| true).unwrap_or_default())
+            .filter(|children| children.iter().all(|walker| walker.node.name == "VariableDeclaration"))
+            .flatten()
+            .collect()
+    }
+}
+
+impl Detector for UninitializedStorageDetector {
+    fn id(&self) -> &str {
+        "uninitialized-storage"
+    }
+
+    fn description(&self) -> &str {
+        "Local storage pointers are not initialized"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    /// The declaration is the primary node, the state variables it overwrites are `alias`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        if !context.allows_version_below(&Version::new(0, 5, 0)) {
+            return vec![];
+        }
+        let states = context.get_states().iter()
+            .filter_map(|state_id| dict.walker_at(*state_id))
+            .filter(|state| !state.node.attributes["constant"].as_bool().unwrap_or(false))
+            .collect::<Vec<&Walker>>();
+        let kinds = states.iter().map(|state| Utils::normalize_kind(state)).collect::<Vec<String>>();
+        let layout = UninitializedStorageDetector::layout(dict, &kinds);
+        let mut findings = vec![];
+        for function in context.get_functions() {
+            for declaration in UninitializedStorageDetector::find_declarations(function) {
+                let kind = Utils::normalize_kind(&declaration);
+                let is_pointer = Utils::find_location(&declaration).starts_with("storage")
+                    && (kind.starts_with("struct ") || kind.ends_with(']'));
+                if !is_pointer {
+                    continue;
+                }
+                // A dynamic array keeps its length in the first slot
+                let slots = match kind.ends_with("[]") {
+                    true => 1,
+                    false => UninitializedStorageDetector::size_of(dict, &kind) / 32,
+                };
+                let aliases = states.iter()
+                    .zip(layout.iter())
+                    .filter(|(_, (start, _))| *start < slots)
+                    .map(|(state, _)| *state)
+                    .collect::<Vec<&Walker>>();
+                let name = declaration.node.attributes["name"].as_str().unwrap_or("");
+                let names = aliases.iter()
+                    .map(|state| format!("`{}`", context.get_state_name(state.node.id)))
+                    .collect::<Vec<String>>();
+                let message = match names.is_empty() {
+                    true => format!("`{}` is an uninitialized storage pointer", name),
+                    false => format!("`{}` is an uninitialized storage pointer, writing it overwrites {}", name, names.join(", ")),
+                };
+                let mut finding = Finding::new(self.id(), self.severity(), Confidence::High, &declaration, &message);
+                for state in aliases {
+                    finding = finding.with_related("alias", state);
+                }
+                findings.push(finding);
+            }
+        }
+        findings
+    }
+}
@@ -1,6 +1,12 @@
 use crate::dfg::Network;
 use crate::core::Action;
 use crate::core::Member;
+use crate::oracle::{
+    Detector,
+    Finding,
+    Severity,
+    Confidence,
+};
 use std::collections::HashMap;
 use std::collections::HashSet;
 
@@ -10,6 +16,17 @@ pub struct UnsafeSendingCondition {
 }
 
 impl UnsafeSendingCondition {
+    /// Members of addresses and builtin functions which may send ether
+    pub const SENDING_METHODS: [&'static str; 7] = [
+        "send",
+        "transfer",
+        "call",
+        "callcode",
+        "delegatecall",
+        "selfdestruct",
+        "suicide",
+    ];
+
     pub fn new(network: &Network) -> Self {
         let mut unsafe_sending_condition = UnsafeSendingCondition {
             block_timestamps: HashSet::new(),
@@ -68,15 +85,9 @@ impl UnsafeSendingCondition {
                     if walker.node.name == "FunctionCall" {
                         for variable in get_variables(vertex_id) {
                             if let Some(last_member) = variable.get_members().last() {
-                                let sending_methods = vec![
-                                    Member::Global(String::from("send")),
-                                    Member::Global(String::from("transfer")),
-                                    Member::Global(String::from("call")),
-                                    Member::Global(String::from("callcode")),
-                                    Member::Global(String::from("delegatecall")),
-                                    Member::Global(String::from("selfdestruct")),
-                                    Member::Global(String::from("suicide")),
-                                ];
+                                let sending_methods = UnsafeSendingCondition::SENDING_METHODS.iter()
+                                    .map(|method| Member::Global(method.to_string()))
+                                    .collect::<Vec<Member>>();
                                 if sending_methods.contains(last_member) {
                                     possible_vul_vertices.insert(vertex_id);
                                     for i in 0..idx {
@@ -134,3 +145,44 @@ impl UnsafeSendingCondition {
         &self.block_timestamps
     }
 }
+
+/// Detector of sending ether under conditions on block.number or block.timestamp
+pub struct UnsafeSendingConditionDetector;
+
+impl Detector for UnsafeSendingConditionDetector {
+    fn id(&self) -> &str {
+        "unsafe-sending-condition"
+    }
+
+    fn description(&self) -> &str {
+        "Sending ether depends on block.number or block.timestamp"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    /// The vulnerable node is the primary node, the node reading the block is the `source`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let dict = network.get_dict();
+        let unsafe_condition = UnsafeSendingCondition::new(network);
+        let dependencies = vec![
+            ("block.number", unsafe_condition.get_block_numbers()),
+            ("block.timestamp", unsafe_condition.get_block_timestamps()),
+        ];
+        let mut findings = vec![];
+        for (variable, dependency) in dependencies {
+            for (send_at, depend_at) in dependency {
+                if let (Some(send_walker), Some(depend_walker)) = (dict.walker_at(*send_at), dict.walker_at(*depend_at)) {
+                    let message = format!("sending ether depends on {}", variable);
+                    let mut finding = Finding::new(self.id(), self.severity(), Confidence::Medium, send_walker, &message);
+                    if send_at != depend_at {
+                        finding = finding.with_related("source", depend_walker);
+                    }
+                    findings.push(finding);
+                }
+            }
+        }
+        findings
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::{ BTreeMap, BTreeSet };
+use crate::dfg::Network;
+use crate::core::{ Utils, Walker };
+use crate::oracle::{
+    CallSite,
+    Confidence,
+    Context,
+    Detector,
+    Finding,
+    Severity,
+};
+
+/// An expression which must not be predictable
+struct Sink<'a> {
+    /// What the expression is used for, e.g `modulo`
+    usage: &'static str,
+    expression: Walker<'a>,
+}
+
+/// A sink derived from values of the block
+struct Randomness {
+    usage: &'static str,
+    /// Vertices of the data flow from the sink to the vertex reading the block
+    flow: Vec<u32>,
+    /// Nodes reading the block
+    sources: Vec<u32>,
+}
+
+/// Detector of randomness derived from values of the block
+///
+/// Miners choose `block.timestamp` and `block.coinbase` and can withhold blocks whose
+/// `blockhash` or `block.difficulty` does not suit them, hashing these values with `keccak256`
+/// does not help. A value derived from them is reported if it is used as a modulo, an array
+/// index, an amount of ether or compared with a guess in a condition
+pub struct WeakRandomnessDetector;
+
+impl WeakRandomnessDetector {
+    /// Name of a node reading a value of the block, e.g `block.difficulty` or `blockhash`
+    fn get_source_name(context: &Context, walker: &Walker) -> Option<String> {
+        let dict = context.get_dict();
+        let is_global = |walker: &Walker| {
+            walker.node.attributes["referencedDeclaration"].as_u32()
+                .and_then(|reference| dict.walker_at(reference))
+                .is_none()
+        };
+        match walker.node.name {
+            "MemberAccess" => {
+                let member_name = walker.node.attributes["member_name"].as_str().unwrap_or("");
+                let is_block = walker.direct_childs(|_| true).unwrap_or_default().first()
+                    .map(|base| {
+                        base.node.name == "Identifier"
+                            && base.node.attributes["value"].as_str() == Some("block")
+                            && is_global(base)
+                    })
+                    .unwrap_or(false);
+                match is_block && matches!(member_name, "blockhash" | "difficulty" | "coinbase" | "timestamp") {
+                    true => Some(format!("block.{}", member_name)),
+                    false => None,
+                }
+            },
+            "Identifier" => {
+                let value = walker.node.attributes["value"].as_str().unwrap_or("");
+                match matches!(value, "blockhash" | "now") && is_global(walker) {
+                    true => Some(value.to_string()),
+                    false => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Nodes reading values of the block in a subtree
+    fn find_sources<'a>(context: &Context, walker: &Walker<'a>) -> Vec<Walker<'a>> {
+        let ig = |_: &Walker, _: &Vec<Walker>| false;
+        let fi = |walker: &Walker, _: &Vec<Walker>| WeakRandomnessDetector::get_source_name(context, walker).is_some();
+        walker.walk(true, ig, fi).unwrap_or_default()
+    }
+
+    /// Modulos, indexes of arrays, amounts of ether and comparisons in conditions of a function
+    fn find_sinks<'a>(context: &'a Context, function: &Walker<'a>) -> Vec<Sink<'a>> {
+        let dict = context.get_dict();
+        let mut sinks = vec![];
+        let ig = |_: &Walker, _: &Vec<Walker>| false;
+        let fi = |walker: &Walker, _: &Vec<Walker>| {
+            matches!(walker.node.name, "BinaryOperation" | "Assignment" | "IndexAccess" | "FunctionCall")
+        };
+        for walker in function.walk(false, ig, fi).unwrap_or_default() {
+            let operator = walker.node.attributes["operator"].as_str().unwrap_or("");
+            let children = walker.direct_childs(|_| true).unwrap_or_default();
+            match walker.node.name {
+                "BinaryOperation" | "Assignment" if matches!(operator, "%" | "%=") => {
+                    sinks.push(Sink { usage: "modulo", expression: walker.clone() });
+                },
+                "IndexAccess" => {
+                    let is_array = children.first()
+                        .map(|base| Utils::normalize_kind(base).ends_with(']'))
+                        .unwrap_or(false);
+                    if let (true, Some(index)) = (is_array, children.get(1)) {
+                        sinks.push(Sink { usage: "array index", expression: index.clone() });
+                    }
+                },
+                "FunctionCall" => {
+                    let value = CallSite::new(&walker)
+                        .and_then(|call_site| call_site.get_value())
+                        .and_then(|value| dict.walker_at(value));
+                    if let Some(value) = value {
+                        sinks.push(Sink { usage: "ether amount", expression: value.clone() });
+                    }
+                },
+                _ => {},
+            }
+        }
+        let mut conditions = BTreeMap::new();
+        for execution_path in context.get_execution_paths(function.node.id) {
+            for vertex_id in execution_path {
+                if let Some(condition) = context.get_condition(*vertex_id) {
+                    conditions.insert(condition.node.id, condition);
+                }
+            }
+        }
+        for (_, condition) in conditions {
+            let fi = |walker: &Walker, _: &Vec<Walker>| {
+                let operator = walker.node.attributes["operator"].as_str().unwrap_or("");
+                walker.node.name == "BinaryOperation" && matches!(operator, "==" | "!=")
+            };
+            for comparison in condition.walk(false, ig, fi).unwrap_or_default() {
+                sinks.push(Sink { usage: "winner selection", expression: comparison });
+            }
+        }
+        sinks
+    }
+
+    /// Data flow from a sink to the values of the block it is derived from
+    fn find_randomness(context: &Context, function: &Walker, sink: &Sink) -> Option<Randomness> {
+        let dict = context.get_dict();
+        let sources = WeakRandomnessDetector::find_sources(context, &sink.expression);
+        if !sources.is_empty() {
+            let sources = sources.iter().map(|source| source.node.id).collect();
+            return Some(Randomness { usage: sink.usage, flow: vec![], sources });
+        }
+        for execution_path in context.get_execution_paths(function.node.id) {
+            let vertex_id = match context.find_vertex(execution_path, &sink.expression) {
+                Some(index) => execution_path[index],
+                None => continue,
+            };
+            for dependent_path in context.get_dependent_paths(vertex_id, &sink.expression) {
+                let mut flow: Vec<u32> = vec![];
+                for (_, dependent_id) in dependent_path.into_iter().skip(1) {
+                    if flow.last() == Some(&dependent_id) || dependent_id == vertex_id {
+                        continue;
+                    }
+                    flow.push(dependent_id);
+                    // Values converted by `uint(..)` or hashed are not linked, the declaration
+                    // reading the block is found from its nodes instead
+                    let sources = dict.walker_at(dependent_id)
+                        .map(|dependent| WeakRandomnessDetector::find_sources(context, dependent))
+                        .unwrap_or_default();
+                    if !sources.is_empty() {
+                        let sources = sources.iter().map(|source| source.node.id).collect();
+                        return Some(Randomness { usage: sink.usage, flow, sources });
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Detector for WeakRandomnessDetector {
+    fn id(&self) -> &str {
+        "weak-randomness"
+    }
+
+    fn description(&self) -> &str {
+        "Randomness is derived from values of the block"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    /// The sink is the primary node, vertices of the data flow are `flow` from the sink to the
+    /// nodes reading the block which are `source`
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        let context = Context::new(network);
+        let dict = context.get_dict();
+        let mut randomnesses: BTreeMap<u32, Randomness> = BTreeMap::new();
+        for function in context.get_functions() {
+            for sink in WeakRandomnessDetector::find_sinks(&context, function) {
+                if randomnesses.contains_key(&sink.expression.node.id) {
+                    continue;
+                }
+                if let Some(randomness) = WeakRandomnessDetector::find_randomness(&context, function, &sink) {
+                    randomnesses.insert(sink.expression.node.id, randomness);
+                }
+            }
+        }
+        let mut findings = vec![];
+        for (sink_id, randomness) in randomnesses {
+            let sink = match dict.walker_at(sink_id) {
+                Some(walker) => walker,
+                None => continue,
+            };
+            let sources = randomness.sources.iter()
+                .filter_map(|source_id| dict.walker_at(*source_id))
+                .collect::<Vec<&Walker>>();
+            let names = sources.iter()
+                .filter_map(|source| WeakRandomnessDetector::get_source_name(&context, source))
+                .map(|name| format!("`{}`", name))
+                .collect::<BTreeSet<String>>();
+            let message = format!(
+                "{} depends on {} which miners can influence",
+                randomness.usage,
+                names.into_iter().collect::<Vec<String>>().join(", "),
+            );
+            let mut finding = Finding::new(self.id(), self.severity(), Confidence::Medium, sink, &message);
+            for vertex in randomness.flow.iter().filter_map(|vertex_id| dict.walker_at(*vertex_id)) {
+                finding = finding.with_related("flow", vertex);
+            }
+            for source in sources {
+                finding = finding.with_related("source", source);
+            }
+            findings.push(finding);
+        }
+        findings
+    }
+}
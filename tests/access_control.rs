@@ -0,0 +1,25 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, AccessControlDetector, Confidence };
+
+#[test]
+fn missing_access_control() -> io::Result<()> {
+    setup_network("access_control_1.sol", "Token", |network| {
+        let findings = AccessControlDetector.check(&network);
+        let lines = findings.iter()
+            .map(|finding| finding.get_node().get_location().get_start().0)
+            .collect::<Vec<usize>>();
+        // `claimOwnership` checks a balance and `takeOwnership` checks the opposite
+        assert_eq!(lines, vec![25, 35, 45, 49, 60, 65]);
+        assert!(findings[0].get_message().contains("`initialize` writes `owner`"));
+        assert_eq!(findings[1].get_confidence(), Confidence::Medium);
+        assert!(findings[2].get_message().contains("`minter`"));
+        assert_eq!(findings[3].get_confidence(), Confidence::Low);
+        assert_eq!(findings[3].get_related_by_role("function").len(), 1);
+        assert!(findings[4].get_message().contains("`claimOwnership` writes `owner`"));
+        assert!(findings[5].get_message().contains("`takeOwnership` writes `owner`"));
+    })?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, ArbitrarySendDetector };
+
+#[test]
+fn arbitrary_send() -> io::Result<()> {
+    setup_network("arbitrary_send_1.sol", "Wallet", |network| {
+        let findings = ArbitrarySendDetector.check(&network);
+        let lines = findings.iter()
+            .map(|finding| finding.get_node().get_location().get_start().0)
+            .collect::<Vec<usize>>();
+        // `payOut` checks the opposite, `sweep` overwrites the recipient with a constant
+        assert_eq!(lines, vec![23, 28, 41]);
+        assert!(findings[0].get_message().contains("`transfer`"));
+        assert!(findings[1].get_message().contains("depends on `to`"));
+        assert_eq!(findings[1].get_related_by_role("recipient").len(), 1);
+        assert!(findings[2].get_message().contains("depends on `to`"));
+    })?;
+    Ok(())
+}
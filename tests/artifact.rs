@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+use ssa::{
+    core::{ Dictionary, Edge },
+    cfg::ControlFlowGraph,
+    loader::{ Artifact, SolidityASTOutput, SolidityError },
+};
+
+fn contract_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/contracts/")
+}
+
+/// Standard json output of `compact_1.sol` with a warning
+fn standard_json_output() -> json::JsonValue {
+    let content = fs::read_to_string(contract_dir().join("compact_1.compact.json")).unwrap();
+    let combined_json = json::parse(&content).unwrap();
+    let mut output = json::JsonValue::new_object();
+    output["errors"] = json::parse(r#"[{
+        "type": "Warning",
+        "severity": "warning",
+        "message": "Function state mutability can be restricted to view",
+        "formattedMessage": "compact_1.sol:5:3: Warning: Function state mutability can be restricted to view"
+    }]"#).unwrap();
+    output["sources"]["compact_1.sol"]["id"] = 0.into();
+    output["sources"]["compact_1.sol"]["ast"] = combined_json["sources"]["compact_1.sol"]["AST"].clone();
+    output
+}
+
+/// Write an artifact to a fresh directory under the temp dir
+fn setup_artifact(name: &str, artifact: &json::JsonValue) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ssa-artifact-{}", name));
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    let artifact_path = dir.join("artifact.json");
+    fs::write(&artifact_path, artifact.dump()).unwrap();
+    artifact_path
+}
+
+/// Control flow graph of `main` in `compact_1.sol`
+fn check_cfg(output: SolidityASTOutput) {
+    let SolidityASTOutput { ast, sources } = output;
+    let ast = json::parse(&ast).unwrap();
+    let dict = Dictionary::new(&ast, &sources).unwrap();
+    let cfg = ControlFlowGraph::new(&dict, 19, 18).unwrap();
+    assert_eq!(cfg.get_vertices().len(), 6);
+    assert!(cfg.get_edges().contains(&Edge::new(10, 14)));
+}
+
+#[test]
+fn standard_json_output_artifact() {
+    let artifact_path = setup_artifact("standard_json", &standard_json_output());
+    let contract_dir = contract_dir();
+    let output = Artifact::new(&artifact_path, &contract_dir).load().unwrap();
+    // Sources are read from the source root
+    let source = fs::read_to_string(contract_dir.join("compact_1.sol")).unwrap();
+    assert_eq!(output.sources.len(), 1);
+    assert_eq!(output.sources["compact_1.sol"], source);
+    check_cfg(output);
+}
+
+#[test]
+fn test_contract_artifact() {
+    let contract_dir = contract_dir();
+    let artifact_path = contract_dir.join("chained_call_1.json");
+    let output = Artifact::new(&artifact_path, &contract_dir).load().unwrap();
+    let source = fs::read_to_string(contract_dir.join("chained_call_1.sol")).unwrap();
+    assert_eq!(output.sources.len(), 1);
+    assert_eq!(output.sources["chained_call_1.sol"], source);
+    let ast = json::parse(&output.ast).unwrap();
+    let dict = Dictionary::new(&ast, &output.sources).unwrap();
+    let cfg = ControlFlowGraph::new(&dict, 15, 14).unwrap();
+    assert!(cfg.get_edges().contains(&Edge::new(10, 11)));
+}
+
+#[test]
+fn build_info_artifact() {
+    let source = fs::read_to_string(contract_dir().join("compact_1.sol")).unwrap();
+    let mut build_info = json::JsonValue::new_object();
+    build_info["_format"] = "hh-sol-build-info-1".into();
+    build_info["solcVersion"] = "0.4.24".into();
+    build_info["input"]["language"] = "Solidity".into();
+    build_info["input"]["sources"]["compact_1.sol"]["content"] = source.as_str().into();
+    build_info["output"] = standard_json_output();
+    let artifact_path = setup_artifact("build_info", &build_info);
+    // Sources are taken from the input, the source root does not have them
+    let source_root = artifact_path.parent().unwrap();
+    let output = Artifact::new(&artifact_path, source_root).load().unwrap();
+    assert_eq!(output.sources["compact_1.sol"], source);
+    check_cfg(output);
+}
+
+#[test]
+fn invalid_artifacts() {
+    let contract_dir = contract_dir();
+    let mut output = standard_json_output();
+    output["errors"][0]["severity"] = "error".into();
+    let artifact_path = setup_artifact("error", &output);
+    match Artifact::new(&artifact_path, &contract_dir).load() {
+        Err(SolidityError::Compiler(messages)) => assert_eq!(messages.len(), 1),
+        _ => panic!("errors of the compiler are ignored"),
+    }
+    let artifact_path = setup_artifact("no_sources", &json::parse(r#"{ "contracts": {} }"#).unwrap());
+    match Artifact::new(&artifact_path, &contract_dir).load() {
+        Err(SolidityError::InvalidArtifact(reason)) => assert!(reason.ends_with("has no sources")),
+        _ => panic!("an artifact without sources is loaded"),
+    }
+}
@@ -0,0 +1,73 @@
+use std::path::{ Path, PathBuf };
+use std::process::{ Command, Output };
+
+fn ssa(args: &[&str]) -> Output {
+    let home_dir = env!("CARGO_MANIFEST_DIR");
+    let bin_dir = Path::new(home_dir).join("tests/bin/");
+    Command::new(env!("CARGO_BIN_EXE_ssa"))
+        .current_dir(home_dir)
+        .arg("--bin-dir")
+        .arg(bin_dir)
+        .args(args)
+        .output()
+        .expect("Can not run ssa")
+}
+
+fn contract(name: &str) -> PathBuf {
+    Path::new("tests/contracts/").join(name)
+}
+
+#[test]
+fn no_finding() {
+    let path = contract("compact_1.legacy.json");
+    let output = ssa(&["--format", "json", path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+    let report = json::parse(&String::from_utf8_lossy(&output.stdout)).unwrap();
+    assert!(report["findings"].is_empty());
+    assert!(report["errors"].is_empty());
+}
+
+#[test]
+fn dot_output() {
+    let path = contract("compact_1.legacy.json");
+    let output = ssa(&["-f", "dot", "-c", "Compact", path.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.starts_with("// Compact\ndigraph {"));
+    assert!(stdout.contains("10 -> 14;"));
+}
+
+#[test]
+fn invalid_arguments() {
+    assert_eq!(ssa(&["--format", "xml", "a.sol"]).status.code(), Some(2));
+    assert_eq!(ssa(&["--oracle", "unknown", "a.sol"]).status.code(), Some(2));
+    assert_eq!(ssa(&[]).status.code(), Some(2));
+}
+
+#[test]
+fn missing_file() {
+    let path = contract("missing.json");
+    assert_eq!(ssa(&[path.to_str().unwrap()]).status.code(), Some(2));
+}
+
+#[test]
+fn findings() {
+    let path = contract("cli_1.sol");
+    let output = ssa(&["--format", "json", path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let report = json::parse(&String::from_utf8_lossy(&output.stdout)).unwrap();
+    assert_eq!(report["findings"][0]["contract"], "Lottery");
+    assert_eq!(report["findings"][0]["detector"], "unsafe-sending-condition");
+    assert_eq!(report["findings"][0]["node"]["location"]["line"], 5);
+}
+
+#[test]
+fn contract_error() {
+    let path = contract("cli_2.json");
+    let output = ssa(&["-f", "dot", path.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("Invalid: "));
+    assert!(stdout.contains("// Compact\ndigraph {"));
+}
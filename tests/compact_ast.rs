@@ -0,0 +1,144 @@
+mod setup;
+
+use std::fs;
+use std::path::Path;
+use std::collections::HashMap;
+use std::os::unix::fs::OpenOptionsExt;
+use setup::{ setup_ast, setup_compact_ast };
+use ssa::{
+    core::{ Dictionary, Edge },
+    cfg::ControlFlowGraph,
+    dfg::Network,
+    loader::{
+        Artifact,
+        Solidity,
+        SolidityASTOutput,
+        SolidityOption,
+        SolidityOutputKind,
+    },
+};
+
+fn load(kind: &str) -> (json::JsonValue, HashMap<String, String>) {
+    let home_dir = env!("CARGO_MANIFEST_DIR");
+    let contract_dir = Path::new(home_dir).join("tests/contracts/");
+    let artifact_path = contract_dir.join(format!("compact_1.{}.json", kind));
+    let artifact = Artifact::new(&artifact_path, &contract_dir);
+    let SolidityASTOutput { ast, sources } = artifact.load().unwrap();
+    (json::parse(&ast).expect("Invalid json format"), sources)
+}
+
+#[test]
+fn same_cfg_for_both_formats() {
+    let (legacy_ast, legacy_sources) = load("legacy");
+    let (compact_ast, compact_sources) = load("compact");
+    let legacy_dict = Dictionary::new(&legacy_ast, &legacy_sources).unwrap();
+    let compact_dict = Dictionary::new(&compact_ast, &compact_sources).unwrap();
+    let legacy_cfg = ControlFlowGraph::new(&legacy_dict, 19, 18).unwrap();
+    let compact_cfg = ControlFlowGraph::new(&compact_dict, 19, 18).unwrap();
+    let stop = compact_cfg.get_stop();
+    assert_eq!(compact_cfg.get_vertices().len(), 6);
+    assert!(compact_cfg.get_edges().contains(&Edge::new(10, 14)));
+    assert!(compact_cfg.get_edges().contains(&Edge::new(10, stop)));
+    assert_eq!(legacy_cfg.get_vertices(), compact_cfg.get_vertices());
+    assert_eq!(legacy_cfg.get_edges(), compact_cfg.get_edges());
+}
+
+#[test]
+fn same_attributes_for_both_formats() {
+    let (legacy_ast, legacy_sources) = load("legacy");
+    let (compact_ast, compact_sources) = load("compact");
+    let legacy_dict = Dictionary::new(&legacy_ast, &legacy_sources).unwrap();
+    let compact_dict = Dictionary::new(&compact_ast, &compact_sources).unwrap();
+    for id in 2..20 {
+        let legacy_walker = legacy_dict.walker_at(id).unwrap();
+        let compact_walker = compact_dict.walker_at(id).unwrap();
+        assert_eq!(legacy_walker.node.name, compact_walker.node.name);
+        assert_eq!(legacy_walker.node.source, compact_walker.node.source);
+        for key in vec!["value", "type", "operator", "referencedDeclaration"] {
+            assert_eq!(legacy_walker.node.attributes[key], compact_walker.node.attributes[key]);
+        }
+        let legacy_childs = legacy_walker.direct_childs(|_| true).unwrap();
+        let compact_childs = compact_walker.direct_childs(|_| true).unwrap();
+        let legacy_ids = legacy_childs.iter().map(|walker| walker.node.id).collect::<Vec<u32>>();
+        let compact_ids = compact_childs.iter().map(|walker| walker.node.id).collect::<Vec<u32>>();
+        assert_eq!(legacy_ids, compact_ids);
+    }
+}
+
+/// Vertices and edges of every function or the error building its graph, and the sorted lines of
+/// the DOT output of every contract
+fn format_graphs(dict: &Dictionary) -> HashMap<u32, Result<Vec<String>, String>> {
+    let mut graphs = HashMap::new();
+    for function in dict.filter_by("FunctionDefinition") {
+        let contract_id = function.node.attributes["scope"].as_u32().unwrap();
+        let cfg = ControlFlowGraph::new(dict, contract_id, function.node.id).map(|cfg| {
+            let vertices = cfg.get_vertices().iter().map(|vertex| format!("{:?}", vertex));
+            let edges = cfg.get_edges().iter().map(|edge| format!("{:?}", edge));
+            let mut lines = vertices.chain(edges).collect::<Vec<String>>();
+            lines.sort();
+            lines
+        });
+        graphs.insert(function.node.id, cfg.map_err(|error| error.to_string()));
+    }
+    for contract in dict.filter_by("ContractDefinition") {
+        let dot = Network::new(dict, contract.node.id).map(|mut network| {
+            let dot = network.format();
+            let mut lines = dot.lines().map(|line| line.to_string()).collect::<Vec<String>>();
+            lines.sort();
+            lines
+        });
+        graphs.insert(contract.node.id, dot.map_err(|error| error.to_string()));
+    }
+    graphs
+}
+
+#[test]
+fn same_cfg_for_test_contracts() {
+    let contract_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/contracts/");
+    let mut names = fs::read_dir(&contract_dir).unwrap()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".sol"))
+        .collect::<Vec<String>>();
+    names.sort();
+    for name in names {
+        let legacy_output = setup_ast(&name).unwrap();
+        let compact_output = setup_compact_ast(&name).unwrap();
+        let legacy_ast = json::parse(&legacy_output.ast).unwrap();
+        let compact_ast = json::parse(&compact_output.ast).unwrap();
+        let legacy_dict = Dictionary::new(&legacy_ast, &legacy_output.sources).unwrap();
+        let compact_dict = Dictionary::new(&compact_ast, &compact_output.sources).unwrap();
+        let legacy_graphs = format_graphs(&legacy_dict);
+        assert!(!legacy_graphs.is_empty(), "{} has no contract", name);
+        assert_eq!(legacy_graphs, format_graphs(&compact_dict), "{} has different graphs", name);
+    }
+}
+
+#[test]
+fn compact_format_argument() {
+    let dir = std::env::temp_dir().join("ssa-compact-arguments");
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    let script = "#!/bin/sh\necho \"$@\" > $0.args\necho '{\"sourceList\":[],\"sources\":{}}'\n";
+    for version in ["v0.5.17", "v0.8.8"].iter() {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .mode(0o777)
+            .open(dir.join(version))
+            .unwrap();
+        std::io::Write::write_all(&mut file, script.as_bytes()).unwrap();
+    }
+    let fields = |version: &str, kind: SolidityOutputKind| {
+        let contract = dir.join("main.sol");
+        fs::write(&contract, format!("pragma solidity {};\ncontract A {{}}", version)).unwrap();
+        let option = SolidityOption::new(&dir, &contract, kind);
+        assert!(Solidity::new(option).compile().is_ok());
+        let args = fs::read_to_string(dir.join(format!("v{}.args", version))).unwrap();
+        args.split_whitespace().nth(1).unwrap().to_string()
+    };
+    assert_eq!(fields("0.5.17", SolidityOutputKind::AST), "ast");
+    assert_eq!(fields("0.5.17", SolidityOutputKind::CompactAST), "ast,compact-format");
+    assert_eq!(fields("0.8.8", SolidityOutputKind::CompactAST), "ast");
+}
@@ -0,0 +1,75 @@
+pragma solidity ^0.4.24;
+
+contract Token {
+  address owner;
+  address minter;
+  mapping(address => bool) operators;
+  mapping(address => uint) balances;
+  bool paused;
+  uint totalSupply;
+
+  modifier onlyOwner() {
+    require(msg.sender == owner);
+    _;
+  }
+
+  constructor() public {
+    owner = msg.sender;
+  }
+
+  function setOwner(address newOwner) public onlyOwner {
+    owner = newOwner;
+  }
+
+  function initialize(address newOwner) public {
+    owner = newOwner;
+  }
+
+  function setMinter(address newMinter) public {
+    if (msg.sender == owner) {
+      minter = newMinter;
+    }
+  }
+
+  function addOperator(address operator) public {
+    operators[operator] = true;
+  }
+
+  function mint(address to, uint amount) public {
+    require(msg.sender == minter || operators[msg.sender]);
+    balances[to] += amount;
+    totalSupply += amount;
+  }
+
+  function changeMinter(address newMinter) public {
+    minter = newMinter;
+  }
+
+  function pause() public {
+    paused = true;
+  }
+
+  function transfer(address to, uint amount) public {
+    require(!paused && balances[msg.sender] >= amount);
+    balances[msg.sender] -= amount;
+    balances[to] += amount;
+  }
+
+  function claimOwnership(address newOwner) public {
+    require(balances[msg.sender] > 0);
+    owner = newOwner;
+  }
+
+  function takeOwnership(address newOwner) public {
+    if (msg.sender != owner) {
+      owner = newOwner;
+    }
+  }
+
+  function transferOwnership(address newOwner) public {
+    if (msg.sender != owner) {
+      revert();
+    }
+    owner = newOwner;
+  }
+}
@@ -0,0 +1,50 @@
+pragma solidity ^0.4.24;
+
+contract Wallet {
+  address owner;
+  mapping(address => uint) balances;
+
+  modifier onlyOwner() {
+    require(msg.sender == owner);
+    _;
+  }
+
+  function deposit() public payable {
+    balances[msg.sender] += msg.value;
+  }
+
+  function withdraw(uint amount) public {
+    require(balances[msg.sender] >= amount);
+    balances[msg.sender] -= amount;
+    msg.sender.transfer(amount);
+  }
+
+  function pay(address to, uint amount) public {
+    to.transfer(amount);
+  }
+
+  function forward(address to) public {
+    address recipient = to;
+    recipient.call.value(address(this).balance)();
+  }
+
+  function refund(address to, uint amount) public onlyOwner {
+    to.send(amount);
+  }
+
+  function payOwner() public {
+    owner.transfer(address(this).balance);
+  }
+
+  function payOut(address to, uint amount) public {
+    if (msg.sender != owner) {
+      to.transfer(amount);
+    }
+  }
+
+  function sweep(address to) public {
+    address recipient = to;
+    recipient = address(0);
+    recipient.call.value(address(this).balance)();
+  }
+}
@@ -0,0 +1,7 @@
+pragma solidity ^0.4.24;
+
+contract ChainedCall {
+  function pay(address a) public {
+    a.call.value(1)();
+  }
+}
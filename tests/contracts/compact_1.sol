@@ -0,0 +1,10 @@
+pragma solidity ^0.4.24;
+
+contract Compact {
+  uint x;
+  function main(uint y) public {
+    if (y > 0) {
+      x = y;
+    }
+  }
+}
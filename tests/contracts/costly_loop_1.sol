@@ -0,0 +1,34 @@
+pragma solidity ^0.4.24;
+
+contract Airdrop {
+  address[] users;
+  mapping(address => uint) balances;
+  uint count;
+  uint[10] fixed;
+
+  function join() public {
+    users.push(msg.sender);
+    count++;
+  }
+
+  function distribute() public {
+    for (uint i = 0; i < users.length; i++) {
+      users[i].transfer(1);
+    }
+  }
+
+  function reset() public {
+    uint total = count;
+    uint i = 0;
+    while (i < total) {
+      balances[users[i]] = 0;
+      i++;
+    }
+  }
+
+  function clear() public {
+    for (uint i = 0; i < fixed.length; i++) {
+      fixed[i] = 0;
+    }
+  }
+}
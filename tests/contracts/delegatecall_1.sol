@@ -0,0 +1,27 @@
+pragma solidity ^0.4.24;
+
+contract Proxy {
+  address owner;
+  address implementation;
+
+  modifier onlyOwner() {
+    require(msg.sender == owner);
+    _;
+  }
+
+  function () public payable {
+    implementation.delegatecall(msg.data);
+  }
+
+  function execute(address target, bytes data) public {
+    target.delegatecall(data);
+  }
+
+  function upgrade(address target) public onlyOwner {
+    target.callcode(bytes4(keccak256("init()")));
+  }
+
+  function migrate() public onlyOwner {
+    implementation.delegatecall(bytes4(keccak256("migrate()")));
+  }
+}
@@ -0,0 +1,78 @@
+pragma solidity ^0.4.24;
+
+contract Market {
+  address owner;
+  uint price;
+  uint reward;
+  mapping(address => uint) balances;
+  mapping(address => mapping(address => uint)) allowed;
+
+  constructor() public {
+    owner = msg.sender;
+    price = 1 ether;
+  }
+
+  function approve(address spender, uint value) public {
+    allowed[msg.sender][spender] = value;
+  }
+
+  function transferFrom(address from, address to, uint value) public {
+    require(value <= allowed[from][msg.sender]);
+    allowed[from][msg.sender] -= value;
+    balances[from] -= value;
+    balances[to] += value;
+  }
+
+  function transfer(address to, uint value) public {
+    require(value <= balances[msg.sender]);
+    balances[msg.sender] -= value;
+    balances[to] += value;
+  }
+
+  function setPrice(uint newPrice) public {
+    require(msg.sender == owner);
+    price = newPrice;
+  }
+
+  function buy() public payable {
+    uint amount = msg.value / price;
+    balances[msg.sender] += amount;
+  }
+
+  function setReward(uint newReward) public {
+    require(msg.sender == owner);
+    reward = newReward;
+  }
+
+  function claim() public {
+    msg.sender.transfer(reward);
+  }
+
+  Token token;
+  uint deadline;
+  bool closed;
+
+  function setOwner(address newOwner) public {
+    require(msg.sender == owner);
+    owner = newOwner;
+  }
+
+  function withdrawTokens(uint value) public {
+    require(msg.sender == owner);
+    token.transfer(owner, value);
+  }
+
+  function setDeadline(uint newDeadline) public {
+    require(msg.sender == owner);
+    deadline = newDeadline;
+  }
+
+  function close() public {
+    require(now >= deadline);
+    closed = true;
+  }
+}
+
+contract Token {
+  function transfer(address to, uint value) public returns (bool);
+}
@@ -0,0 +1,65 @@
+pragma solidity ^0.4.24;
+
+library SafeMath {
+  function add(uint a, uint b) internal pure returns (uint) {
+    uint c = a + b;
+    assert(c >= a);
+    return c;
+  }
+}
+
+contract Token {
+  using SafeMath for uint;
+  mapping(address => uint) balances;
+  uint totalSupply;
+  uint price;
+
+  function buy() public payable {
+    balances[msg.sender] += msg.value * price;
+  }
+
+  function transfer(address to, uint value) public {
+    require(balances[msg.sender] >= value);
+    balances[msg.sender] -= value;
+    balances[to] = balances[to].add(value);
+  }
+
+  function withdraw(uint amount) public {
+    if (amount <= balances[msg.sender]) {
+      balances[msg.sender] -= amount;
+    }
+  }
+
+  function burn(uint amount) public {
+    balances[msg.sender] -= amount;
+  }
+
+  function mint() internal {
+    totalSupply = totalSupply + 1;
+  }
+
+  function drain(uint amount) public {
+    require(balances[msg.sender] < amount);
+    balances[msg.sender] -= amount;
+  }
+
+  function spend(uint amount) public {
+    balances[msg.sender] -= amount;
+    if (balances[msg.sender] > amount) {}
+  }
+
+  modifier hasBalance(uint amount) {
+    require(balances[msg.sender] >= amount);
+    _;
+  }
+
+  function pay(uint amount) public hasBalance(amount) {
+    balances[msg.sender] -= amount;
+  }
+
+  function deposit(uint amount) public {
+    uint balance = balances[msg.sender] + amount;
+    require(balance >= amount);
+    balances[msg.sender] = balance;
+  }
+}
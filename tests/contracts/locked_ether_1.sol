@@ -0,0 +1,43 @@
+pragma solidity ^0.4.24;
+
+library Payments {
+  function pay(address to, uint amount) public {
+    to.transfer(amount);
+  }
+
+  function fee(uint amount) public pure returns (uint) {
+    return amount / 100;
+  }
+}
+
+contract Locked {
+  mapping(address => uint) balances;
+
+  function deposit() public payable {
+    balances[msg.sender] += msg.value - Payments.fee(msg.value);
+  }
+
+  function () public payable {
+  }
+}
+
+contract Proxy is Locked {
+  address implementation;
+
+  function forward() public {
+    implementation.delegatecall(msg.data);
+  }
+}
+
+contract Vault is Locked {
+  function withdraw(uint amount) public {
+    Payments.pay(msg.sender, amount);
+  }
+}
+
+contract Bank is Locked {
+  function withdraw(uint amount) public {
+    balances[msg.sender] -= amount;
+    msg.sender.transfer(amount);
+  }
+}
@@ -0,0 +1,68 @@
+pragma solidity ^0.4.24;
+
+contract Token {
+  function transfer(address to, uint amount) public returns (bool);
+}
+
+contract Bank {
+  mapping(address => uint) balances;
+  uint withdrawals;
+  Token token;
+  bool locked;
+  bool paused;
+  bool entered;
+
+  modifier noReentrancy() {
+    require(!locked);
+    locked = true;
+    _;
+    locked = false;
+  }
+
+  modifier whenUnlocked() {
+    require(!paused);
+    _;
+  }
+
+  modifier once() {
+    require(entered == false);
+    entered = true;
+    _;
+    entered = false;
+  }
+
+  function withdraw() public {
+    uint amount = balances[msg.sender];
+    require(msg.sender.call.value(amount)());
+    balances[msg.sender] = 0;
+  }
+
+  function reward(address to) public {
+    token.transfer(to, 1);
+    withdrawals += 1;
+  }
+
+  function safeWithdraw() public {
+    uint amount = balances[msg.sender];
+    balances[msg.sender] = 0;
+    msg.sender.transfer(amount);
+  }
+
+  function guardedWithdraw() public noReentrancy {
+    uint amount = balances[msg.sender];
+    require(msg.sender.call.value(amount)());
+    balances[msg.sender] = 0;
+  }
+
+  function pausableWithdraw() public whenUnlocked {
+    uint amount = balances[msg.sender];
+    require(msg.sender.call.value(amount)());
+    balances[msg.sender] = 0;
+  }
+
+  function onceWithdraw() public once {
+    uint amount = balances[msg.sender];
+    require(msg.sender.call.value(amount)());
+    balances[msg.sender] = 0;
+  }
+}
@@ -0,0 +1,65 @@
+pragma solidity ^0.4.24;
+
+contract Killable {
+  address owner;
+  mapping(address => bool) admins;
+  mapping(address => uint) balances;
+
+  modifier onlyOwner() {
+    require(msg.sender == owner);
+    _;
+  }
+
+  function kill() public onlyOwner {
+    selfdestruct(owner);
+  }
+
+  function close() public {
+    if (admins[msg.sender]) {
+      selfdestruct(msg.sender);
+    }
+  }
+
+  function destroy() public {
+    address caller = msg.sender;
+    if (caller != owner) {
+      revert();
+    }
+    suicide(owner);
+  }
+
+  function shutdown(uint code) public {
+    if (code == 42) {
+      selfdestruct(msg.sender);
+    }
+  }
+
+  function terminate() external {
+    selfdestruct(owner);
+  }
+
+  function quit() public {
+    if (msg.sender != owner) {
+      selfdestruct(owner);
+    }
+  }
+
+  function leave() public {
+    if (msg.sender == owner) {
+    }
+    selfdestruct(owner);
+  }
+
+  function withdrawAll() public {
+    require(balances[msg.sender] > 0);
+    selfdestruct(msg.sender);
+  }
+
+  function retire() public {
+    if (!admins[msg.sender]) {
+      revert();
+    } else {
+      selfdestruct(owner);
+    }
+  }
+}
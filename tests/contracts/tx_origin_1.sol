@@ -0,0 +1,31 @@
+pragma solidity ^0.4.24;
+
+contract Wallet {
+  address owner;
+  uint limit;
+
+  constructor() public {
+    owner = tx.origin;
+  }
+
+  modifier onlyOwner() {
+    require(tx.origin == owner);
+    _;
+  }
+
+  function withdraw(uint amount) public {
+    address sender = tx.origin;
+    if (sender == owner) {
+      msg.sender.transfer(amount);
+    }
+  }
+
+  function setLimit(uint value) public onlyOwner {
+    limit = value;
+  }
+
+  function reset() public {
+    require(tx.origin == msg.sender);
+    limit = 0;
+  }
+}
@@ -0,0 +1,34 @@
+pragma solidity ^0.4.24;
+
+contract Payout {
+  mapping(address => uint) balances;
+  bool sent;
+
+  function pay(address to, uint amount) public {
+    to.send(amount);
+  }
+
+  function forward(address to, bytes data) public {
+    bool success = to.call(data);
+    balances[to] = 0;
+  }
+
+  function checkedPay(address to, uint amount) public {
+    if (!to.send(amount)) {
+      revert();
+    }
+  }
+
+  function checkedForward(address to, bytes data) public {
+    bool success = to.call(data);
+    require(success);
+  }
+
+  function storedPay(address to, uint amount) public {
+    sent = to.send(amount);
+  }
+
+  function verify() public {
+    require(sent);
+  }
+}
@@ -0,0 +1,30 @@
+pragma solidity ^0.4.24;
+
+contract NameRegistrar {
+  bool unlocked;
+  address owner;
+  uint fee;
+  mapping(bytes32 => address) names;
+
+  struct Record {
+    bytes32 name;
+    address mappedAddress;
+  }
+
+  function register(bytes32 name, address mappedAddress) public {
+    Record newRecord;
+    newRecord.name = name;
+    newRecord.mappedAddress = mappedAddress;
+    names[name] = mappedAddress;
+  }
+
+  function push(uint value) public {
+    uint[] storage values;
+    values.push(value);
+  }
+
+  function copy(bytes32 name) public {
+    Record memory record;
+    record.name = name;
+  }
+}
@@ -0,0 +1,34 @@
+pragma solidity ^0.4.24;
+
+contract Lottery {
+  address[] players;
+  address winner;
+  uint seed;
+
+  function enter() public payable {
+    players.push(msg.sender);
+  }
+
+  function draw() public {
+    uint random = uint(keccak256(block.difficulty, now));
+    uint index = random % players.length;
+    winner = players[index];
+  }
+
+  function guess(uint number) public payable {
+    uint answer = uint(blockhash(block.number - 1));
+    if (number == answer) {
+      msg.sender.transfer(address(this).balance);
+    }
+  }
+
+  function bonus() public {
+    uint amount = uint(block.coinbase) % 10;
+    msg.sender.transfer(amount);
+  }
+
+  function pick(uint count) public {
+    seed = count % 10;
+    winner = players[seed];
+  }
+}
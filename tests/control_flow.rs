@@ -0,0 +1,22 @@
+mod setup;
+
+use std::io;
+use setup::setup_cfg;
+use ssa::core::{ Edge, Shape };
+
+#[test]
+fn chained_call() -> io::Result<()> {
+    setup_cfg("chained_call_1.sol", 14, |cfg| {
+        let mut function_calls = cfg.get_vertices().iter()
+            .filter(|v| v.get_shape() == &Shape::DoubleCircle)
+            .map(|v| v.get_id())
+            .collect::<Vec<u32>>();
+        function_calls.sort();
+        // `a.call.value(1)` and the call of its result
+        assert_eq!(function_calls, vec![10, 11]);
+        assert!(cfg.get_edges().contains(&Edge::new(9, 10)));
+        assert!(cfg.get_edges().contains(&Edge::new(10, 11)));
+        assert!(cfg.get_edges().contains(&Edge::new(11, 12)));
+    })?;
+    Ok(())
+}
@@ -0,0 +1,23 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, CostlyLoopDetector };
+
+#[test]
+fn growing_loops() -> io::Result<()> {
+    setup_network("costly_loop_1.sol", "Airdrop", |network| {
+        let findings = CostlyLoopDetector.check(&network);
+        assert_eq!(findings.len(), 3);
+        assert_eq!(findings[0].get_node().get_source(), "i < users.length");
+        assert!(findings[0].get_message().contains("length of `users`"));
+        assert_eq!(findings[1].get_node().get_source(), "i < total");
+        assert!(findings[1].get_message().contains("counter `count`"));
+        let transfer = &findings[2];
+        assert_eq!(transfer.get_node().get_source(), "users[i].transfer(1)");
+        let headers = transfer.get_related_by_role("loop");
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].get_source(), "i < users.length");
+    })?;
+    Ok(())
+}
@@ -12,11 +12,11 @@ fn depend_on_state_variable() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 5);
         assert_eq!(edges.len(), 4);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 1);
         for link in links {
-            assert_eq!(link.get_from(), 9);
-            assert_eq!(link.get_to(), 3);
+            assert_eq!(link.get_from().1, 9);
+            assert_eq!(link.get_to().1, 3);
         } 
     })?;
     Ok(())
@@ -30,11 +30,11 @@ fn depend_on_parameters() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 4);
         assert_eq!(edges.len(), 3);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 1);
         for link in links {
-            assert_eq!(link.get_from(), 9);
-            assert_eq!(link.get_to(), 4);
+            assert_eq!(link.get_from().1, 9);
+            assert_eq!(link.get_to().1, 4);
         }
     })?;
     Ok(())
@@ -48,11 +48,11 @@ fn depend_on_local_variables() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 5);
         assert_eq!(edges.len(), 4);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 1);
         for link in links {
-            assert_eq!(link.get_from(), 10);
-            assert_eq!(link.get_to(), 8);
+            assert_eq!(link.get_from().1, 10);
+            assert_eq!(link.get_to().1, 8);
         }
     })?;
     Ok(())
@@ -66,13 +66,13 @@ fn find_assignments() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 7);
         assert_eq!(edges.len(), 6);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 3);
         for link in links {
-            match link.get_from() {
-                19 => assert_eq!(link.get_to(), 17),
-                17 => assert_eq!(link.get_to(), 6),
-                13 => assert_eq!(link.get_to(), 3),
+            match link.get_from().1 {
+                19 => assert_eq!(link.get_to().1, 17),
+                17 => assert_eq!(link.get_to().1, 6),
+                13 => assert_eq!(link.get_to().1, 3),
                 _ => assert!(false),
             }
         }
@@ -88,24 +88,24 @@ fn struct_assignments() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 11);
         assert_eq!(edges.len(), 10);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 8);
         for link in links {
-            match link.get_from() {
-                22 => match link.get_to() {
+            match link.get_from().1 {
+                22 => match link.get_to().1 {
                     13 | 7 => {},
                     _ => assert!(false),
                 },
-                50 => match link.get_to() {
+                50 => match link.get_to().1 {
                     7 | 44 => {},
                     _ => assert!(false),
                 },
-                38 => match link.get_to() {
+                38 => match link.get_to().1 {
                     32 | 7 => {},
                     _ => assert!(false),
                 }
-                32 => assert_eq!(link.get_to(), 13),
-                28 => assert_eq!(link.get_to(), 16),
+                32 => assert_eq!(link.get_to().1, 13),
+                28 => assert_eq!(link.get_to().1, 16),
                 _ => assert!(false),
             }
         }
@@ -121,15 +121,15 @@ fn array_assignments() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 7);
         assert_eq!(edges.len(), 6);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 4);
         for link in links {
-            match link.get_from() {
-                16 => match link.get_to() {
+            match link.get_from().1 {
+                16 => match link.get_to().1 {
                     4 | 7 => {},
                     _ => assert!(false),
                 },
-                29 => match link.get_to() {
+                29 => match link.get_to().1 {
                     7 | 23 => {},
                     _ => assert!(false),
                 },
@@ -148,12 +148,12 @@ fn variables_in_functioncall() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 9);
         assert_eq!(edges.len(), 8);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 3);
         for link in links {
-            match link.get_from() {
-                40 => assert_eq!(26, link.get_to()),
-                44 => match link.get_to() {
+            match link.get_from().1 {
+                40 => assert_eq!(26, link.get_to().1),
+                44 => match link.get_to().1 {
                     30 | 26 => {},
                     _ => assert!(false),
                 },
@@ -172,15 +172,15 @@ fn unary_operator() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 9);
         assert_eq!(edges.len(), 8);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 5);
         for link in links {
-            match link.get_from() {
-                31 => assert_eq!(27, link.get_to()),
-                27 => assert_eq!(22, link.get_to()),
-                22 => assert_eq!(17, link.get_to()),
-                17 => assert_eq!(12, link.get_to()),
-                12 => assert_eq!(7, link.get_to()),
+            match link.get_from().1 {
+                31 => assert_eq!(27, link.get_to().1),
+                27 => assert_eq!(22, link.get_to().1),
+                22 => assert_eq!(17, link.get_to().1),
+                17 => assert_eq!(12, link.get_to().1),
+                12 => assert_eq!(7, link.get_to().1),
                 _ => assert!(false),
             }
         }
@@ -196,12 +196,12 @@ fn unary_in_functioncall() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 6);
         assert_eq!(edges.len(), 5);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 2);
         for link in links {
-            match link.get_from() {
-                20 => assert_eq!(15, link.get_to()),
-                15 => assert_eq!(7, link.get_to()),
+            match link.get_from().1 {
+                20 => assert_eq!(15, link.get_to().1),
+                15 => assert_eq!(7, link.get_to().1),
                 _ => assert!(false),
             }
         }
@@ -217,11 +217,11 @@ fn delete_call() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 6);
         assert_eq!(edges.len(), 5);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 1);
         for link in links {
-            match link.get_from() {
-                11 => assert_eq!(9, link.get_to()),
+            match link.get_from().1 {
+                11 => assert_eq!(9, link.get_to().1),
                 _ => assert!(false),
             }
         }
@@ -237,16 +237,16 @@ fn find_variables_in_index_acccess() -> io::Result<()> {
         let stop = cfg.get_stop();
         assert_eq!(vertices.len(), 7);
         assert_eq!(edges.len(), 6);
-        let links = DataFlowGraph::new(cfg).find_links();
+        let links = DataFlowGraph::new(cfg).find_links().unwrap();
         assert_eq!(links.len(), 5);
         for link in links {
-            match link.get_from() {
-                39 => match link.get_to() {
+            match link.get_from().1 {
+                39 => match link.get_to().1 {
                     24 | 5 => {},
                     _ => assert!(false),
                 },
-                36 => assert_eq!(24, link.get_to()),
-                41 => assert_eq!(39, link.get_to()),
+                36 => assert_eq!(24, link.get_to().1),
+                41 => assert_eq!(39, link.get_to().1),
                 _ => assert!(false),
             }
         }
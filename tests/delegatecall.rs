@@ -0,0 +1,23 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, DelegatecallDetector, Severity };
+
+#[test]
+fn controlled_delegatecall() -> io::Result<()> {
+    setup_network("delegatecall_1.sol", "Proxy", |network| {
+        let findings = DelegatecallDetector.check(&network);
+        assert_eq!(findings.len(), 3);
+        let fallback = &findings[0];
+        assert_eq!(fallback.get_severity(), Severity::Medium);
+        assert!(fallback.get_message().contains("calldata depends on `msg.data`"));
+        let execute = &findings[1];
+        assert_eq!(execute.get_node().get_source(), "target.delegatecall(data)");
+        assert_eq!(execute.get_severity(), Severity::High);
+        let upgrade = &findings[2];
+        assert_eq!(upgrade.get_severity(), Severity::Medium);
+        assert!(!upgrade.get_message().contains("msg.sender"));
+    })?;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use std::path::Path;
+use std::collections::HashMap;
+use ssa::{
+    core::{ Dictionary, Error, Walker },
+    cfg::ControlFlowGraph,
+    loader::{ Artifact, SolidityASTOutput },
+};
+
+fn load() -> (json::JsonValue, HashMap<String, String>) {
+    let home_dir = env!("CARGO_MANIFEST_DIR");
+    let contract_dir = Path::new(home_dir).join("tests/contracts/");
+    let artifact_path = contract_dir.join("compact_1.legacy.json");
+    let artifact = Artifact::new(&artifact_path, &contract_dir);
+    let SolidityASTOutput { ast, sources } = artifact.load().unwrap();
+    (json::parse(&ast).expect("Invalid json format"), sources)
+}
+
+fn find_node(value: &mut json::JsonValue, id: u32) -> Option<&mut json::JsonValue> {
+    if value["id"].as_u32() == Some(id) {
+        return Some(value);
+    }
+    let children: Vec<&mut json::JsonValue> = match value {
+        json::JsonValue::Object(object) => object.iter_mut().map(|(_, child)| child).collect(),
+        json::JsonValue::Array(array) => array.iter_mut().collect(),
+        _ => vec![],
+    };
+    children.into_iter().find_map(|child| find_node(child, id))
+}
+
+#[test]
+fn malformed_source_range() {
+    let (mut ast, sources) = load();
+    find_node(&mut ast, 10).unwrap()["src"] = "100000:5:0".into();
+    match Dictionary::new(&ast, &sources) {
+        Err(Error::MalformedSource { id: Some(10), location }) => assert_eq!(location, "100000:5:0"),
+        other => panic!("unexpected result {:?}", other.err()),
+    }
+}
+
+#[test]
+fn malformed_child() {
+    let (mut ast, sources) = load();
+    find_node(&mut ast, 10).unwrap()["src"] = "100000:5:0".into();
+    let source = &sources["compact_1.sol"];
+    let walker = Walker::new(find_node(&mut ast, 16).unwrap(), source, "compact_1.sol").unwrap();
+    match walker.direct_childs(|_| true) {
+        Err(Error::MalformedSource { id: Some(10), .. }) => {},
+        other => panic!("unexpected result {:?}", other.err()),
+    }
+    assert!(walker.walk(false, |_, _| false, |_, _| true).is_err());
+}
+
+#[test]
+fn unknown_function() {
+    let (ast, sources) = load();
+    let dict = Dictionary::new(&ast, &sources).unwrap();
+    match ControlFlowGraph::new(&dict, 19, 1000) {
+        Err(error @ Error::UnknownFunction(1000)) => assert_eq!(error.to_string(), "unknown function (id: 1000)"),
+        other => panic!("unexpected result {:?}", other.err()),
+    }
+}
@@ -0,0 +1,34 @@
+use std::path::Path;
+use ssa::{
+    core::Dictionary,
+    oracle::{ Finding, Severity, Confidence },
+    loader::{ Artifact, SolidityASTOutput },
+};
+
+#[test]
+fn finding_with_related_nodes() {
+    let home_dir = env!("CARGO_MANIFEST_DIR");
+    let contract_dir = Path::new(home_dir).join("tests/contracts/");
+    let artifact_path = contract_dir.join("compact_1.legacy.json");
+    let SolidityASTOutput { ast, sources } = Artifact::new(&artifact_path, &contract_dir).load().unwrap();
+    let ast = json::parse(&ast).unwrap();
+    let dict = Dictionary::new(&ast, &sources).unwrap();
+    let condition = dict.walker_at(10).unwrap();
+    let parameter = dict.walker_at(5).unwrap();
+    let assignment = dict.walker_at(13).unwrap();
+    let finding = Finding::new("test", Severity::Low, Confidence::High, condition, "condition on y")
+        .with_related("source", parameter)
+        .with_related("sink", assignment);
+    assert_eq!(finding.get_node().get_id(), 10);
+    assert_eq!(finding.get_node().get_source(), "y > 0");
+    assert_eq!(finding.get_related_by_role("sink")[0].get_id(), 13);
+    assert_eq!(finding.get_locations().len(), 3);
+    assert_eq!(finding.to_string(), "compact_1.sol:6:9: [test] condition on y");
+    let value = finding.to_json();
+    assert_eq!(value["severity"], "low");
+    assert_eq!(value["confidence"], "high");
+    assert_eq!(value["node"]["location"]["line"], 6);
+    assert_eq!(value["related"][0]["role"], "source");
+    assert_eq!(value["related"][0]["id"], 5);
+    assert!(Severity::High > Severity::Medium);
+}
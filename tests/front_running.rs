@@ -0,0 +1,21 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, FrontRunningDetector };
+
+#[test]
+fn transaction_ordering_dependence() -> io::Result<()> {
+    setup_network("front_running_1.sol", "Market", |network| {
+        let findings = FrontRunningDetector.check(&network);
+        let lines = findings.iter()
+            .map(|finding| finding.get_node().get_location().get_start().0)
+            .collect::<Vec<usize>>();
+        assert_eq!(lines, vec![20, 39, 48]);
+        assert!(findings[0].get_message().contains("`transferFrom` depends on `allowed` which `approve` sets"));
+        assert_eq!(findings[0].get_related_by_role("write")[0].get_location().get_start().0, 16);
+        assert!(findings[1].get_message().contains("`price`"));
+        assert!(findings[2].get_message().starts_with("ether transfer in `claim`"));
+    })?;
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::os::unix::fs::OpenOptionsExt;
+use ssa::loader::{
+    CompilerProvider,
+    LocalProvider,
+    Solidity,
+    SolidityError,
+    SolidityOption,
+    SolidityOutputKind,
+    Version,
+};
+
+/// Write `files` to a fresh directory under the temp dir
+fn setup_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ssa-import-{}", name));
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    for (path, source) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, source).unwrap();
+    }
+    dir
+}
+
+/// A library whose pragma tells which file is imported
+fn library(version: &str) -> String {
+    format!("pragma solidity {};\nlibrary L {{}}", version)
+}
+
+/// Provider listing the releases of 0.5 which never has a compiler
+struct ReleasedProvider;
+
+impl CompilerProvider for ReleasedProvider {
+    fn provide(&self, _: &str) -> Result<Option<PathBuf>, SolidityError> {
+        Ok(None)
+    }
+
+    fn versions(&self) -> Result<Vec<Version>, SolidityError> {
+        Ok((0..=17).map(|patch| Version::new(0, 5, patch)).collect())
+    }
+}
+
+/// Version picked for `contract`, an imported file is found if its pragma is part of it
+fn resolve(contract: &Path, setup: impl FnOnce(&mut SolidityOption)) -> String {
+    let mut option = SolidityOption::new(Path::new("bin"), contract, SolidityOutputKind::AST);
+    setup(&mut option);
+    let providers: Vec<Box<dyn CompilerProvider>> = vec![Box::new(ReleasedProvider)];
+    match Solidity::with_providers(option, providers).compile() {
+        Err(SolidityError::CompilerNotFound(version)) => version,
+        _ => panic!("no compiler is expected"),
+    }
+}
+
+#[test]
+fn longest_prefix_remapping() {
+    let dir = setup_dir("longest", &[
+        ("main.sol", "pragma solidity ^0.5.0;\nimport \"@lib/math/safe.sol\";\ncontract A {}"),
+        ("a/math/safe.sol", &library("0.5.1")),
+        ("b/safe.sol", &library("0.5.2")),
+    ]);
+    let remappings = vec![
+        format!("@lib/={}/a/", dir.display()),
+        format!("@lib/math/={}/b/", dir.display()),
+        format!("@l={}/c/", dir.display()),
+    ];
+    let version = resolve(&dir.join("main.sol"), |option| option.remappings = remappings.clone());
+    assert_eq!(version, "v0.5.2");
+    let version = resolve(&dir.join("main.sol"), |option| option.remappings = remappings[..1].to_vec());
+    assert_eq!(version, "v0.5.1");
+}
+
+#[test]
+fn context_prefixed_remapping() {
+    let dir = setup_dir("context", &[
+        ("main.sol", "pragma solidity ^0.5.0;\nimport \"@lib/safe.sol\";\ncontract A {}"),
+        ("dep/main.sol", "pragma solidity ^0.5.0;\nimport \"@lib/safe.sol\";\ncontract B {}"),
+        ("v1/safe.sol", &library("0.5.1")),
+        ("v2/safe.sol", &library("0.5.2")),
+    ]);
+    let remappings = vec![
+        format!("{}/dep:@lib/={}/v1/", dir.display(), dir.display()),
+        format!("@lib/={}/v2/", dir.display()),
+    ];
+    let version = resolve(&dir.join("dep/main.sol"), |option| option.remappings = remappings.clone());
+    assert_eq!(version, "v0.5.1");
+    let version = resolve(&dir.join("main.sol"), |option| option.remappings = remappings.clone());
+    assert_eq!(version, "v0.5.2");
+    // The context wins over a longer prefix
+    let mut remappings = remappings.clone();
+    remappings.push(format!("@lib/safe={}/v2/safe", dir.display()));
+    let version = resolve(&dir.join("dep/main.sol"), |option| option.remappings = remappings.clone());
+    assert_eq!(version, "v0.5.1");
+}
+
+#[test]
+fn base_path_before_include_paths() {
+    let dir = setup_dir("roots", &[
+        ("main.sol", "pragma solidity ^0.5.0;\nimport \"lib/safe.sol\";\nimport \"@lib/token.sol\";\ncontract A {}"),
+        ("base/lib/safe.sol", &library("<=0.5.3")),
+        ("include/lib/safe.sol", &library("<=0.5.2")),
+        ("include/@lib/token.sol", &library("<=0.5.6")),
+        ("remapped/token.sol", &library("<=0.5.1")),
+    ]);
+    let main = dir.join("main.sol");
+    let base_path = Some(dir.join("base"));
+    let include_paths = vec![dir.join("empty"), dir.join("include")];
+    let version = resolve(&main, |option| {
+        option.base_path = base_path.clone();
+        option.include_paths = include_paths.clone();
+    });
+    assert_eq!(version, "v0.5.3");
+    // Include paths are tried in order after the base path
+    let version = resolve(&main, |option| option.include_paths = include_paths.clone());
+    assert_eq!(version, "v0.5.2");
+    // A path found as it is wins over a remapped path
+    let version = resolve(&main, |option| {
+        option.base_path = base_path.clone();
+        option.include_paths = include_paths.clone();
+        option.remappings = vec![format!("@lib/={}/remapped/", dir.display())];
+    });
+    assert_eq!(version, "v0.5.3");
+    fs::write(dir.join("include/lib/safe.sol"), library("<=0.5.0")).unwrap();
+    fs::remove_file(dir.join("base/lib/safe.sol")).unwrap();
+    let version = resolve(&main, |option| {
+        option.base_path = base_path.clone();
+        option.include_paths = include_paths.clone();
+    });
+    assert_eq!(version, "v0.5.0");
+}
+
+#[test]
+fn import_arguments() {
+    let dir = setup_dir("arguments", &[]);
+    let bin_dir = dir.join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    let script = "#!/bin/sh\necho \"$@\" > $0.args\necho '{\"sourceList\":[],\"sources\":{}}'\n";
+    for version in ["v0.5.17", "v0.6.9", "v0.8.8"].iter() {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .mode(0o777)
+            .open(bin_dir.join(version))
+            .unwrap();
+        std::io::Write::write_all(&mut file, script.as_bytes()).unwrap();
+    }
+    let args = |version: &str| {
+        let contract = dir.join(format!("{}.sol", version));
+        fs::write(&contract, format!("pragma solidity {};\ncontract A {{}}", version)).unwrap();
+        let providers: Vec<Box<dyn CompilerProvider>> = vec![Box::new(LocalProvider::new(&bin_dir))];
+        let mut option = SolidityOption::new(&bin_dir, &contract, SolidityOutputKind::AST);
+        option.base_path = Some(PathBuf::from("base"));
+        option.include_paths = vec![PathBuf::from("lib")];
+        option.remappings = vec!["@lib/=node_modules/lib/".to_string()];
+        assert!(Solidity::with_providers(option, providers).compile().is_ok());
+        let args = fs::read_to_string(bin_dir.join(format!("v{}.args", version))).unwrap();
+        args.trim().replace(&dir.display().to_string(), "<dir>")
+    };
+    let allowed_paths = "--allow-paths <dir>,base,lib,node_modules/lib/ @lib/=node_modules/lib/ <dir>";
+    assert_eq!(args("0.5.17"), format!("--combined-json ast {}/0.5.17.sol", allowed_paths));
+    assert_eq!(args("0.6.9"), format!("--combined-json ast --base-path base {}/0.6.9.sol", allowed_paths));
+    assert_eq!(
+        args("0.8.8"),
+        format!("--combined-json ast --base-path base --include-path lib {}/0.8.8.sol", allowed_paths),
+    );
+}
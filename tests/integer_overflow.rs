@@ -0,0 +1,34 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, IntegerOverflowDetector, Severity };
+
+#[test]
+fn unguarded_arithmetic() -> io::Result<()> {
+    setup_network("integer_overflow_1.sol", "Token", |network| {
+        let findings = IntegerOverflowDetector.check(&network);
+        let sources = findings.iter()
+            .map(|finding| finding.get_node().get_source())
+            .collect::<Vec<&str>>();
+        assert_eq!(findings.len(), 6);
+        assert!(sources.contains(&"balances[msg.sender] += msg.value * price"));
+        // A bound in the wrong direction or after the operation does not guard it
+        let underflows = findings.iter()
+            .filter(|finding| finding.get_node().get_source() == "balances[msg.sender] -= amount")
+            .map(|finding| finding.get_node().get_location().get_start().0)
+            .collect::<Vec<usize>>();
+        assert_eq!(underflows, vec![34, 43, 47]);
+        let multiplication = findings.iter()
+            .find(|finding| finding.get_node().get_source() == "msg.value * price")
+            .unwrap();
+        assert_eq!(multiplication.get_severity(), Severity::High);
+        assert!(multiplication.get_message().contains("`msg.value`"));
+        let increment = findings.iter()
+            .find(|finding| finding.get_node().get_source() == "totalSupply + 1")
+            .unwrap();
+        assert_eq!(increment.get_severity(), Severity::Medium);
+        assert!(increment.get_related_by_role("source").is_empty());
+    })?;
+    Ok(())
+}
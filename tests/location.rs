@@ -0,0 +1,49 @@
+use std::path::Path;
+use std::collections::HashMap;
+use ssa::{
+    core::{ Dictionary, Location },
+    cfg::ControlFlowGraph,
+    loader::{ Artifact, SolidityASTOutput },
+};
+
+fn load() -> (json::JsonValue, HashMap<String, String>) {
+    let home_dir = env!("CARGO_MANIFEST_DIR");
+    let contract_dir = Path::new(home_dir).join("tests/contracts/");
+    let artifact_path = contract_dir.join("compact_1.legacy.json");
+    let artifact = Artifact::new(&artifact_path, &contract_dir);
+    let SolidityASTOutput { ast, sources } = artifact.load().unwrap();
+    (json::parse(&ast).expect("Invalid json format"), sources)
+}
+
+#[test]
+fn location_from_byte_range() {
+    let source = "a\nbcé\nd";
+    let location = Location::new("a.sol", source, 2, 6);
+    assert_eq!(location.get_range(), (2, 6));
+    assert_eq!(location.get_start(), (2, 1));
+    assert_eq!(location.get_end(), (2, 4));
+    assert_eq!(location.to_string(), "a.sol:2:1");
+}
+
+#[test]
+fn node_location() {
+    let (ast, sources) = load();
+    let dict = Dictionary::new(&ast, &sources).unwrap();
+    let walker = dict.walker_at(16).unwrap();
+    let location = walker.get_location();
+    assert_eq!(walker.node.file, "compact_1.sol");
+    assert_eq!(walker.node.get_range(), 92..123);
+    assert_eq!(location.get_start(), (6, 5));
+    assert_eq!(location.get_end(), (8, 6));
+}
+
+#[test]
+fn vertex_location() {
+    let (ast, sources) = load();
+    let dict = Dictionary::new(&ast, &sources).unwrap();
+    let cfg = ControlFlowGraph::new(&dict, 19, 18).unwrap();
+    let vertex = cfg.get_vertices().iter().find(|vertex| vertex.get_id() == 10).unwrap();
+    assert_eq!(vertex.get_location().get_file(), "compact_1.sol");
+    assert_eq!(vertex.get_location().get_start(), (6, 9));
+    assert_eq!(vertex.get_location().get_end(), (6, 14));
+}
@@ -0,0 +1,30 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, LockedEtherDetector, Confidence };
+
+#[test]
+fn locked_ether() -> io::Result<()> {
+    setup_network("locked_ether_1.sol", "Locked", |network| {
+        let findings = LockedEtherDetector.check(&network);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].get_confidence(), Confidence::High);
+        assert_eq!(findings[0].get_related_by_role("payable").len(), 2);
+    })?;
+    setup_network("locked_ether_1.sol", "Proxy", |network| {
+        let findings = LockedEtherDetector.check(&network);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].get_confidence(), Confidence::Low);
+        assert_eq!(findings[0].get_related_by_role("escape")[0].get_location().get_start().0, 28);
+    })?;
+    setup_network("locked_ether_1.sol", "Vault", |network| {
+        let findings = LockedEtherDetector.check(&network);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].get_related_by_role("escape")[0].get_location().get_start().0, 34);
+    })?;
+    setup_network("locked_ether_1.sol", "Bank", |network| {
+        assert!(LockedEtherDetector.check(&network).is_empty());
+    })?;
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use std::fs;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::path::{ Path, PathBuf };
+use std::os::unix::fs::OpenOptionsExt;
+use ssa::loader::{
+    CompilerProvider,
+    DownloadProvider,
+    LocalProvider,
+    PathProvider,
+    Solidity,
+    SolidityError,
+    SolidityOption,
+    SolidityOutput,
+    SolidityOutputKind,
+    Version,
+};
+
+/// Provider which records that it was asked and fails
+struct FailingProvider {
+    calls: Rc<RefCell<Vec<String>>>,
+}
+
+impl CompilerProvider for FailingProvider {
+    fn provide(&self, version: &str) -> Result<Option<PathBuf>, SolidityError> {
+        self.calls.borrow_mut().push(version.to_string());
+        Err(SolidityError::Download("offline".to_string()))
+    }
+}
+
+/// Provider which records that it was asked, it lists `versions` but has no compiler
+struct EmptyProvider {
+    calls: Rc<RefCell<Vec<String>>>,
+    versions: Vec<Version>,
+}
+
+impl CompilerProvider for EmptyProvider {
+    fn provide(&self, version: &str) -> Result<Option<PathBuf>, SolidityError> {
+        self.calls.borrow_mut().push(version.to_string());
+        Ok(None)
+    }
+
+    fn versions(&self) -> Result<Vec<Version>, SolidityError> {
+        Ok(self.versions.clone())
+    }
+}
+
+/// A fresh directory under the temp dir
+fn setup_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ssa-provider-{}", name));
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A fake compiler reporting `version`, each run is appended to `<path>.runs`
+fn setup_compiler(path: &Path, version: &str) {
+    let script = format!(
+        "#!/bin/sh\necho run >> {}.runs\nif [ \"$1\" = \"--version\" ]; then\n  echo \"Version: {}+commit.e67f0147.Linux.g++\"\nelse\n  echo '{{\"sourceList\":[],\"sources\":{{}}}}'\nfi\n",
+        path.display(),
+        version,
+    );
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .mode(0o777)
+        .open(path)
+        .unwrap();
+    std::io::Write::write_all(&mut file, script.as_bytes()).unwrap();
+}
+
+fn count_runs(path: &Path) -> usize {
+    let runs = PathBuf::from(format!("{}.runs", path.display()));
+    fs::read_to_string(runs).map(|runs| runs.lines().count()).unwrap_or(0)
+}
+
+#[test]
+fn local_provider() {
+    let dir = setup_dir("local");
+    for name in ["v0.4.24", "v0.5.1", "solc", "vnext"].iter() {
+        fs::write(dir.join(name), "").unwrap();
+    }
+    let provider = LocalProvider::new(&dir);
+    let mut versions = provider.versions().unwrap();
+    versions.sort();
+    assert_eq!(versions, vec![Version::new(0, 4, 24), Version::new(0, 5, 1)]);
+    assert_eq!(provider.provide("v0.4.24").unwrap(), Some(dir.join("v0.4.24")));
+    assert_eq!(provider.provide("v0.4.25").unwrap(), None);
+    assert!(LocalProvider::new(&dir.join("missing")).versions().unwrap().is_empty());
+}
+
+#[test]
+fn download_provider() {
+    let dir = setup_dir("download");
+    let list = r#"{
+        "builds": [],
+        "releases": {
+            "0.8.30": "solc-linux-amd64-v0.8.30+commit.73712a01",
+            "0.4.10": "solc-linux-amd64-v0.4.10+commit.f0d539ae"
+        },
+        "latestRelease": "0.8.30"
+    }"#;
+    fs::write(dir.join("list.json"), list).unwrap();
+    fs::write(dir.join("v0.4.24"), "").unwrap();
+    // The list was just written so it is not downloaded again
+    let provider = DownloadProvider::new(&dir);
+    let mut versions = provider.versions().unwrap();
+    versions.sort();
+    assert_eq!(versions, vec![Version::new(0, 4, 10), Version::new(0, 8, 30)]);
+    assert_eq!(provider.provide("v0.4.9").unwrap(), None);
+    assert_eq!(provider.provide("v0.4.24").unwrap(), Some(dir.join("v0.4.24")));
+}
+
+#[test]
+fn path_provider() {
+    let dir = setup_dir("path");
+    let compiler_path = dir.join("solc");
+    setup_compiler(&compiler_path, "0.5.3");
+    let provider = PathProvider::new(&compiler_path);
+    assert_eq!(provider.versions().unwrap(), vec![Version::new(0, 5, 3)]);
+    assert_eq!(provider.provide("v0.5.3").unwrap(), Some(compiler_path.clone()));
+    assert_eq!(provider.provide("v0.4.24").unwrap(), None);
+    assert_eq!(count_runs(&compiler_path), 1);
+    let provider = PathProvider::new(&dir.join("missing"));
+    assert!(provider.versions().unwrap().is_empty());
+    assert_eq!(provider.provide("v0.5.3").unwrap(), None);
+}
+
+#[test]
+fn providers_in_order() {
+    let dir = setup_dir("order");
+    let contract = dir.join("main.sol");
+    fs::write(&contract, "pragma solidity ^0.5.0;\ncontract A {}").unwrap();
+    let bin_dir = dir.join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    setup_compiler(&bin_dir.join("v0.5.3"), "0.5.3");
+    let calls = Rc::new(RefCell::new(vec![]));
+    // The local compiler satisfies the pragma, later providers are not asked
+    let providers: Vec<Box<dyn CompilerProvider>> = vec![
+        Box::new(EmptyProvider { calls: calls.clone(), versions: vec![] }),
+        Box::new(LocalProvider::new(&bin_dir)),
+        Box::new(FailingProvider { calls: calls.clone() }),
+    ];
+    let option = SolidityOption::new(&bin_dir, &contract, SolidityOutputKind::AST);
+    match Solidity::with_providers(option, providers).compile() {
+        Ok(SolidityOutput::AST(output)) => assert!(output.sources.is_empty()),
+        _ => panic!("the local compiler is not used"),
+    }
+    assert_eq!(*calls.borrow(), vec!["v0.5.3"]);
+    assert_eq!(count_runs(&bin_dir.join("v0.5.3")), 1);
+    // An error of a provider stops the search
+    calls.borrow_mut().clear();
+    let providers: Vec<Box<dyn CompilerProvider>> = vec![
+        Box::new(FailingProvider { calls: calls.clone() }),
+        Box::new(LocalProvider::new(&bin_dir)),
+    ];
+    let option = SolidityOption::new(&bin_dir, &contract, SolidityOutputKind::AST);
+    match Solidity::with_providers(option, providers).compile() {
+        Err(SolidityError::Download(reason)) => assert_eq!(reason, "offline"),
+        _ => panic!("the failing provider is not asked first"),
+    }
+    assert_eq!(*calls.borrow(), vec!["v0.5.3"]);
+}
+
+#[test]
+fn compiler_not_found() {
+    let dir = setup_dir("not_found");
+    let contract = dir.join("main.sol");
+    fs::write(&contract, "pragma solidity 0.4.25;\ncontract A {}").unwrap();
+    let calls = Rc::new(RefCell::new(vec![]));
+    let providers: Vec<Box<dyn CompilerProvider>> = vec![
+        Box::new(EmptyProvider { calls: calls.clone(), versions: vec![Version::new(0, 4, 25)] }),
+        Box::new(LocalProvider::new(&dir)),
+    ];
+    let option = SolidityOption::new(&dir, &contract, SolidityOutputKind::AST);
+    let error = Solidity::with_providers(option, providers).compile().err().unwrap();
+    assert_eq!(error.to_string(), "no compiler found for v0.4.25");
+    assert!(matches!(error, SolidityError::CompilerNotFound(ref version) if version == "v0.4.25"));
+    assert_eq!(*calls.borrow(), vec!["v0.4.25"]);
+}
@@ -0,0 +1,28 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, ReentrancyDetector, Severity };
+
+#[test]
+fn write_after_external_call() -> io::Result<()> {
+    setup_network("reentrancy_1.sol", "Bank", |network| {
+        let findings = ReentrancyDetector.check(&network);
+        // `guardedWithdraw` and `onceWithdraw` are protected by mutexes, `whenUnlocked` is not one
+        assert_eq!(findings.len(), 3);
+        let withdraw = &findings[0];
+        assert_eq!(withdraw.get_node().get_source(), "msg.sender.call.value(amount)()");
+        assert_eq!(withdraw.get_severity(), Severity::High);
+        let writes = withdraw.get_related_by_role("write");
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].get_location().get_start().0, 37);
+        let reward = &findings[1];
+        assert_eq!(reward.get_node().get_source(), "token.transfer(to, 1)");
+        assert_eq!(reward.get_severity(), Severity::Low);
+        assert!(reward.get_message().contains("`withdrawals`"));
+        let pausable = &findings[2];
+        assert_eq!(pausable.get_node().get_location().get_start().0, 59);
+        assert_eq!(pausable.get_severity(), Severity::High);
+    })?;
+    Ok(())
+}
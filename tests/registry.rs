@@ -0,0 +1,78 @@
+use std::path::Path;
+use ssa::{
+    core::Dictionary,
+    dfg::Network,
+    oracle::{ Detector, Finding, Severity, Confidence, Registry },
+    loader::{ Artifact, SolidityASTOutput },
+};
+
+/// Report every assignment
+struct AssignmentDetector;
+
+impl Detector for AssignmentDetector {
+    fn id(&self) -> &str {
+        "assignment"
+    }
+
+    fn description(&self) -> &str {
+        "Assignments"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Informational
+    }
+
+    fn check(&self, network: &Network) -> Vec<Finding> {
+        network.get_dict().filter_by("Assignment").into_iter()
+            .map(|walker| Finding::new(self.id(), self.severity(), Confidence::High, walker, "assignment"))
+            .collect()
+    }
+}
+
+#[test]
+fn register_and_run_detectors() {
+    let home_dir = env!("CARGO_MANIFEST_DIR");
+    let contract_dir = Path::new(home_dir).join("tests/contracts/");
+    let artifact_path = contract_dir.join("compact_1.legacy.json");
+    let SolidityASTOutput { ast, sources } = Artifact::new(&artifact_path, &contract_dir).load().unwrap();
+    let ast = json::parse(&ast).unwrap();
+    let dict = Dictionary::new(&ast, &sources).unwrap();
+    let network = Network::new(&dict, 19).unwrap();
+    let mut registry = Registry::with_builtins();
+    registry.register(Box::new(AssignmentDetector));
+    let ids = registry.list().iter().map(|detector| detector.id().to_string()).collect::<Vec<String>>();
+    assert_eq!(ids.last().unwrap(), "assignment");
+    let findings = registry.run(&network);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].get_node().get_id(), 13);
+    assert!(registry.disable("assignment"));
+    assert!(!registry.is_enabled("assignment"));
+    assert!(registry.run(&network).is_empty());
+    assert!(!registry.enable("unknown"));
+    registry.register(Box::new(AssignmentDetector));
+    assert_eq!(registry.list().len(), ids.len());
+    assert!(registry.is_enabled("assignment"));
+}
+
+#[test]
+fn builtin_detectors() {
+    let registry = Registry::with_builtins();
+    let mut ids = registry.list().iter().map(|detector| detector.id()).collect::<Vec<&str>>();
+    ids.sort_unstable();
+    assert_eq!(ids, vec![
+        "arbitrary-send",
+        "controlled-delegatecall",
+        "costly-loop",
+        "front-running",
+        "integer-overflow",
+        "locked-ether",
+        "missing-access-control",
+        "reentrancy",
+        "tx-origin",
+        "unchecked-call",
+        "uninitialized-storage",
+        "unprotected-selfdestruct",
+        "unsafe-sending-condition",
+        "weak-randomness",
+    ]);
+}
@@ -0,0 +1,20 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, SelfdestructDetector };
+
+#[test]
+fn unprotected_selfdestruct() -> io::Result<()> {
+    setup_network("selfdestruct_1.sol", "Killable", |network| {
+        let findings = SelfdestructDetector.check(&network);
+        let lines = findings.iter()
+            .map(|finding| finding.get_node().get_location().get_start().0)
+            .collect::<Vec<usize>>();
+        // `quit` checks the opposite, `leave` does not branch on the check and `withdrawAll`
+        // checks a balance
+        assert_eq!(lines, vec![33, 38, 43, 50, 55]);
+        assert!(findings[1].get_message().contains("`selfdestruct`"));
+    })?;
+    Ok(())
+}
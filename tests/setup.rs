@@ -1,9 +1,13 @@
 use std::io::*;
+use std::fs;
+use std::env;
 use std::path::Path;
 use ssa:: {
     core::{ Dictionary },
     cfg::{ ControlFlowGraph },
+    dfg::{ Network },
     loader::{
+        Artifact,
         Solidity,
         SolidityOption,
         SolidityOutputKind,
@@ -12,24 +16,67 @@ use ssa:: {
     },
 };
 
-pub fn setup_cfg<T>(name: &str, entry_id: u32, mut cb: T) -> Result<()> where T: FnMut(ControlFlowGraph) {
+/// Compile a test contract, a compiler output in `contracts/<name>.json` is used if it exists
+pub fn setup_ast(name: &str) -> Result<SolidityASTOutput> {
+    load_or_compile(name, "json", SolidityOutputKind::AST)
+}
+
+/// Compile a test contract to the compact AST, `contracts/<name>.compact.json` is used if it exists
+#[allow(dead_code)]
+pub fn setup_compact_ast(name: &str) -> Result<SolidityASTOutput> {
+    load_or_compile(name, "compact.json", SolidityOutputKind::CompactAST)
+}
+
+/// Load the artifact of a test contract or compile it, the compiler output is saved as the
+/// artifact when `SSA_RECORD_ARTIFACTS` is set so that tests run offline once it is committed
+fn load_or_compile(name: &str, extension: &str, kind: SolidityOutputKind) -> Result<SolidityASTOutput> {
     let home_dir = env!("CARGO_MANIFEST_DIR");
     let tests_dir = Path::new(home_dir).join("tests/");
     let contract_dir = tests_dir.join("contracts/");
-    let option = SolidityOption {
-        bin_dir: &tests_dir.join("bin/"),
-        contract: &contract_dir.join(name),
-        kind: SolidityOutputKind::AST,
-    };
+    let artifact_path = contract_dir.join(name).with_extension(extension);
+    if artifact_path.exists() {
+        let artifact = Artifact::new(&artifact_path, &contract_dir);
+        return Ok(artifact.load()?);
+    }
+    let bin_dir = tests_dir.join("bin/");
+    let contract = contract_dir.join(name);
+    let option = SolidityOption::new(&bin_dir, &contract, kind);
     let solidity = Solidity::new(option);
-    let solidity_output = solidity.compile()?;
-    match solidity_output {
-        SolidityOutput::AST(SolidityASTOutput { ast, sources }) => {
-            let ast_json = json::parse(&ast).expect("Invalid json format");
-            let dict = Dictionary::new(&ast_json, &sources);
-            let mut control_flow = ControlFlowGraph::new(&dict, entry_id);
-            cb(control_flow)
-        }
+    let ast_output = match solidity.compile()? {
+        SolidityOutput::AST(ast_output) => ast_output,
+        SolidityOutput::StandardJson(_) => unreachable!(),
+    };
+    if env::var_os("SSA_RECORD_ARTIFACTS").is_some() {
+        // Source paths are made relative to the contract directory, the source root of artifacts
+        let contract_dir = contract_dir.to_string_lossy();
+        fs::write(&artifact_path, ast_output.ast.replace(contract_dir.as_ref(), ""))?;
     }
+    Ok(ast_output)
+}
+
+pub fn setup_cfg<T>(name: &str, entry_id: u32, mut cb: T) -> Result<()> where T: FnMut(ControlFlowGraph) {
+    let SolidityASTOutput { ast, sources } = setup_ast(name)?;
+    let ast_json = json::parse(&ast).expect("Invalid json format");
+    let dict = Dictionary::new(&ast_json, &sources).map_err(Error::other)?;
+    let contract_id = dict.walker_at(entry_id)
+        .and_then(|walker| walker.node.attributes["scope"].as_u32())
+        .expect("Entry is not a function");
+    let control_flow = ControlFlowGraph::new(&dict, contract_id, entry_id).map_err(Error::other)?;
+    cb(control_flow);
     Ok(())
 } 
+
+/// Build the network of contract `contract_name`
+#[allow(dead_code)]
+pub fn setup_network<T>(name: &str, contract_name: &str, mut cb: T) -> Result<()> where T: FnMut(Network) {
+    let SolidityASTOutput { ast, sources } = setup_ast(name)?;
+    let ast_json = json::parse(&ast).expect("Invalid json format");
+    let dict = Dictionary::new(&ast_json, &sources).map_err(Error::other)?;
+    let contract_id = dict.filter_by("ContractDefinition").into_iter()
+        .find(|walker| walker.node.attributes["name"].as_str() == Some(contract_name))
+        .map(|walker| walker.node.id)
+        .expect("Contract is not found");
+    let network = Network::new(&dict, contract_id).map_err(Error::other)?;
+    cb(network);
+    Ok(())
+}
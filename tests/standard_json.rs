@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::os::unix::fs::OpenOptionsExt;
+use ssa::loader::{
+    CompilerMessage,
+    CompilerProvider,
+    LocalProvider,
+    MessageSeverity,
+    Solidity,
+    SolidityError,
+    SolidityOption,
+    SolidityOutput,
+    SolidityOutputKind,
+};
+
+/// Output of `--standard-json` mode with a warning and an error
+const OUTPUT: &str = r#"{
+    "errors": [
+        {
+            "type": "Warning",
+            "severity": "warning",
+            "message": "Unused local variable.",
+            "formattedMessage": "main.sol:3:9: Warning: Unused local variable.",
+            "sourceLocation": { "file": "main.sol", "start": 48, "end": 54 }
+        },
+        {
+            "type": "TypeError",
+            "severity": "error",
+            "message": "Undeclared identifier.",
+            "formattedMessage": "main.sol:4:9: TypeError: Undeclared identifier."
+        }
+    ],
+    "sources": {}
+}"#;
+
+/// A fresh directory under the temp dir
+fn setup_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ssa-standard-json-{}", name));
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A fake compiler which saves its input to `<path>.input` and prints `output`
+fn setup_compiler(path: &Path, output: &str) {
+    let output_path = PathBuf::from(format!("{}.output", path.display()));
+    fs::write(&output_path, output).unwrap();
+    let script = format!("#!/bin/sh\ncat > {}.input\ncat {}\n", path.display(), output_path.display());
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .mode(0o777)
+        .open(path)
+        .unwrap();
+    std::io::Write::write_all(&mut file, script.as_bytes()).unwrap();
+}
+
+fn compile(dir: &Path, contract: &Path, remappings: Vec<String>) -> Result<SolidityOutput, SolidityError> {
+    let providers: Vec<Box<dyn CompilerProvider>> = vec![Box::new(LocalProvider::new(dir))];
+    let mut option = SolidityOption::new(dir, contract, SolidityOutputKind::StandardJson);
+    option.remappings = remappings;
+    option.optimizer = Some(500);
+    Solidity::with_providers(option, providers).compile()
+}
+
+#[test]
+fn messages_from_output() {
+    let output = json::parse(OUTPUT).unwrap();
+    let messages = CompilerMessage::from_output(&output);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].severity, MessageSeverity::Warning);
+    assert_eq!(messages[0].kind, "Warning");
+    assert_eq!(messages[0].message, "Unused local variable.");
+    assert_eq!(messages[0].location, Some(("main.sol".to_string(), 48, 54)));
+    assert_eq!(messages[1].severity, MessageSeverity::Error);
+    assert_eq!(messages[1].kind, "TypeError");
+    assert_eq!(messages[1].formatted_message, "main.sol:4:9: TypeError: Undeclared identifier.");
+    assert_eq!(messages[1].location, None);
+    assert!(CompilerMessage::from_output(&json::parse("{}").unwrap()).is_empty());
+}
+
+#[test]
+fn errors_fail_compilation() {
+    let dir = setup_dir("errors");
+    let contract = dir.join("main.sol");
+    fs::write(&contract, "pragma solidity 0.4.24;\ncontract A {}").unwrap();
+    setup_compiler(&dir.join("v0.4.24"), OUTPUT);
+    match compile(&dir, &contract, vec![]) {
+        Err(SolidityError::Compiler(messages)) => {
+            let severities = messages.iter().map(|message| message.severity.clone()).collect::<Vec<MessageSeverity>>();
+            assert_eq!(severities, vec![MessageSeverity::Warning, MessageSeverity::Error]);
+        },
+        _ => panic!("errors of the compiler are ignored"),
+    }
+}
+
+#[test]
+fn standard_json_input() {
+    let dir = setup_dir("input");
+    let contract = dir.join("main.sol");
+    let source = "pragma solidity ^0.4.24;\nimport \"@lib/math.sol\";\ncontract A {}";
+    fs::write(&contract, source).unwrap();
+    let path = contract.to_string_lossy().to_string();
+    let output = format!(
+        r#"{{ "errors": [{{ "type": "Warning", "severity": "warning", "message": "", "formattedMessage": "" }}], "sources": {{ "{}": {{ "id": 0 }} }} }}"#,
+        path,
+    );
+    setup_compiler(&dir.join("v0.4.24"), &output);
+    let remappings = vec!["@lib/=node_modules/lib/".to_string()];
+    match compile(&dir, &contract, remappings) {
+        Ok(SolidityOutput::StandardJson(output)) => {
+            assert_eq!(output.sources[&path], source);
+            assert_eq!(output.messages.len(), 1);
+            assert_eq!(output.messages[0].severity, MessageSeverity::Warning);
+        },
+        _ => panic!("warnings fail compilation"),
+    }
+    let input = fs::read_to_string(dir.join("v0.4.24.input")).unwrap();
+    let input = json::parse(&input).unwrap();
+    assert_eq!(input["language"], "Solidity");
+    assert_eq!(input["sources"].len(), 1);
+    assert_eq!(input["sources"][path.as_str()]["content"], source);
+    assert_eq!(input["settings"]["remappings"], json::array!["@lib/=node_modules/lib/"]);
+    assert_eq!(input["settings"]["optimizer"]["enabled"], true);
+    assert_eq!(input["settings"]["optimizer"]["runs"], 500);
+    assert_eq!(input["settings"]["outputSelection"].len(), 1);
+    assert_eq!(input["settings"]["outputSelection"]["*"].len(), 1);
+    assert_eq!(input["settings"]["outputSelection"]["*"][""], json::array!["legacyAST"]);
+}
+
+#[test]
+fn standard_json_input_since_0_8() {
+    let dir = setup_dir("input_0_8");
+    let contract = dir.join("main.sol");
+    fs::write(&contract, "pragma solidity 0.8.4;\ncontract A {}").unwrap();
+    setup_compiler(&dir.join("v0.8.4"), r#"{ "sources": {} }"#);
+    assert!(compile(&dir, &contract, vec![]).is_ok());
+    let input = fs::read_to_string(dir.join("v0.8.4.input")).unwrap();
+    let input = json::parse(&input).unwrap();
+    assert_eq!(input["settings"]["outputSelection"]["*"][""], json::array!["ast"]);
+    assert!(input["settings"]["remappings"].is_empty());
+}
@@ -0,0 +1,52 @@
+use std::fs;
+use std::env;
+use std::path::Path;
+use std::collections::HashMap;
+use ssa::{
+    core::{ Dictionary, Tracer, TraceEvent },
+    dfg::Network,
+    loader::{ Artifact, SolidityASTOutput },
+};
+
+fn load() -> (json::JsonValue, HashMap<String, String>) {
+    let home_dir = env!("CARGO_MANIFEST_DIR");
+    let contract_dir = Path::new(home_dir).join("tests/contracts/");
+    let artifact_path = contract_dir.join("compact_1.legacy.json");
+    let artifact = Artifact::new(&artifact_path, &contract_dir);
+    let SolidityASTOutput { ast, sources } = artifact.load().unwrap();
+    (json::parse(&ast).expect("Invalid json format"), sources)
+}
+
+#[test]
+fn tracer_is_disabled_by_default() {
+    let (ast, sources) = load();
+    let dict = Dictionary::new(&ast, &sources).unwrap();
+    Network::new(&dict, 19).unwrap();
+    assert!(!dict.get_tracer().is_enabled());
+    assert!(dict.get_tracer().get_events().is_empty());
+}
+
+#[test]
+fn tracer_records_analysis() {
+    let (ast, sources) = load();
+    let mut dict = Dictionary::new(&ast, &sources).unwrap();
+    dict.set_tracer(Tracer::enabled());
+    let network = Network::new(&dict, 19).unwrap();
+    let events = dict.get_tracer().get_events();
+    assert!(events.iter().any(|event| match event {
+        TraceEvent::FlatVariable { variables, .. } => !variables.is_empty(),
+        _ => false,
+    }));
+    assert!(events.iter().any(|event| match event {
+        TraceEvent::Actions { actions, .. } => !actions.is_empty(),
+        _ => false,
+    }));
+    let traced_links = events.iter().filter(|event| match event {
+        TraceEvent::Link(link) => network.get_links().contains(link),
+        _ => false,
+    }).count();
+    assert!(traced_links > 0);
+    let path = env::temp_dir().join("ssa_trace.txt");
+    dict.get_tracer().dump(&path).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), dict.get_tracer().format());
+}
@@ -0,0 +1,23 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, TxOriginDetector, Severity };
+
+#[test]
+fn authorization_by_origin() -> io::Result<()> {
+    setup_network("tx_origin_1.sol", "Wallet", |network| {
+        let findings = TxOriginDetector.check(&network);
+        assert_eq!(findings.len(), 2);
+        let modifier = &findings[0];
+        assert_eq!(modifier.get_node().get_source(), "require(tx.origin == owner)");
+        assert_eq!(modifier.get_severity(), Severity::Medium);
+        let withdraw = &findings[1];
+        assert_eq!(withdraw.get_node().get_source(), "sender == owner");
+        assert_eq!(withdraw.get_severity(), Severity::High);
+        let sources = withdraw.get_related_by_role("source");
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].get_location().get_start().0, 17);
+    })?;
+    Ok(())
+}
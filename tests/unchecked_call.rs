@@ -0,0 +1,21 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, UncheckedCallDetector, Confidence };
+
+#[test]
+fn unchecked_return_value() -> io::Result<()> {
+    setup_network("unchecked_call_1.sol", "Payout", |network| {
+        let findings = UncheckedCallDetector.check(&network);
+        assert_eq!(findings.len(), 2);
+        let discarded = &findings[0];
+        assert_eq!(discarded.get_node().get_location().get_start().0, 8);
+        assert_eq!(discarded.get_confidence(), Confidence::High);
+        assert!(discarded.get_message().contains("`send` is discarded"));
+        let stored = &findings[1];
+        assert_eq!(stored.get_node().get_source(), "to.call(data)");
+        assert_eq!(stored.get_related_by_role("assignment").len(), 1);
+    })?;
+    Ok(())
+}
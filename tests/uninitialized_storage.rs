@@ -0,0 +1,21 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, UninitializedStorageDetector };
+
+#[test]
+fn uninitialized_storage_pointer() -> io::Result<()> {
+    setup_network("uninitialized_storage_1.sol", "NameRegistrar", |network| {
+        let findings = UninitializedStorageDetector.check(&network);
+        assert_eq!(findings.len(), 2);
+        let record = &findings[0];
+        assert_eq!(record.get_node().get_location().get_start().0, 15);
+        assert!(record.get_message().contains("`unlocked`, `owner`, `fee`"));
+        assert_eq!(record.get_related_by_role("alias").len(), 3);
+        let values = &findings[1];
+        assert_eq!(values.get_node().get_source(), "uint[] storage values");
+        assert_eq!(values.get_related_by_role("alias").len(), 2);
+    })?;
+    Ok(())
+}
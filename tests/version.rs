@@ -0,0 +1,192 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+use ssa::loader::{
+    CompilerProvider,
+    Solidity,
+    SolidityError,
+    SolidityOption,
+    SolidityOutputKind,
+    Version,
+    VersionReq,
+};
+
+fn matches(req: &str, version: &str) -> bool {
+    let req = VersionReq::parse(req).expect("Invalid version constraint");
+    let version = Version::parse(version).expect("Invalid version");
+    req.matches(&version)
+}
+
+#[test]
+fn caret_constraint() {
+    assert!(matches("^0.4.24", "0.4.24"));
+    assert!(matches("^0.4.24", "0.4.26"));
+    assert!(!matches("^0.4.24", "0.4.23"));
+    assert!(!matches("^0.4.24", "0.5.0"));
+    assert!(matches("^0.0.3", "0.0.3"));
+    assert!(!matches("^0.0.3", "0.0.4"));
+}
+
+#[test]
+fn range_constraint() {
+    assert!(matches(">=0.4.22 <0.6.0", "0.4.22"));
+    assert!(matches(">=0.4.22 <0.6.0", "0.5.17"));
+    assert!(!matches(">=0.4.22 <0.6.0", "0.6.0"));
+    assert!(matches(">= 0.4.22 < 0.6.0", "0.5.0"));
+    assert!(matches("0.4.0 - 0.4.26", "0.4.26"));
+    assert!(!matches("0.4.0 - 0.4", "0.5.0"));
+    assert!(matches(">0.4", "0.5.0"));
+    assert!(!matches(">0.4", "0.4.26"));
+    assert!(matches("<=0.4", "0.4.26"));
+}
+
+#[test]
+fn exact_constraint() {
+    assert!(matches("=0.5.1", "0.5.1"));
+    assert!(!matches("=0.5.1", "0.5.2"));
+    assert!(matches("0.4.25", "0.4.25"));
+    assert!(matches("0.4", "0.4.11"));
+    assert!(matches("0.4.x", "0.4.11"));
+    assert!(matches("*", "0.8.0"));
+}
+
+#[test]
+fn alternative_constraint() {
+    assert!(matches("^0.4.24 || =0.5.1", "0.5.1"));
+    assert!(!matches("^0.4.24 || =0.5.1", "0.5.2"));
+    assert!(matches("~0.5.0 || ~0.6.0", "0.6.12"));
+}
+
+#[test]
+fn invalid_constraint() {
+    assert!(VersionReq::parse("^0.4.24-nightly").is_none());
+    assert!(VersionReq::parse("abc").is_none());
+    assert!(Version::parse("v0.4.24").is_some());
+}
+
+/// Provider with compilers of `versions` which never runs them
+struct CachedProvider {
+    versions: Vec<Version>,
+}
+
+impl CachedProvider {
+    fn new(versions: &[&str]) -> Box<dyn CompilerProvider> {
+        let versions = versions.iter().map(|version| Version::parse(version).unwrap()).collect();
+        Box::new(CachedProvider { versions })
+    }
+
+    /// Releases of 0.4 and 0.5 with a linux build
+    fn released() -> Box<dyn CompilerProvider> {
+        let mut versions = (10..=26).map(|patch| Version::new(0, 4, patch)).collect::<Vec<_>>();
+        versions.extend((0..=17).map(|patch| Version::new(0, 5, patch)));
+        Box::new(CachedProvider { versions })
+    }
+}
+
+impl CompilerProvider for CachedProvider {
+    fn provide(&self, _: &str) -> Result<Option<PathBuf>, SolidityError> {
+        Ok(None)
+    }
+
+    fn versions(&self) -> Result<Vec<Version>, SolidityError> {
+        Ok(self.versions.clone())
+    }
+}
+
+/// Provider which can not list its versions
+struct OfflineProvider;
+
+impl CompilerProvider for OfflineProvider {
+    fn provide(&self, _: &str) -> Result<Option<PathBuf>, SolidityError> {
+        Ok(None)
+    }
+
+    fn versions(&self) -> Result<Vec<Version>, SolidityError> {
+        Err(SolidityError::Download("offline".to_string()))
+    }
+}
+
+/// Write `files` to a fresh directory under the temp dir
+fn setup_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ssa-version-{}", name));
+    fs::remove_dir_all(&dir).ok();
+    for (path, source) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, source).unwrap();
+    }
+    dir
+}
+
+/// Version picked for `contract`, providers never have a compiler so it ends up in the error
+fn extract_version(contract: &Path, providers: Vec<Box<dyn CompilerProvider>>) -> Result<String, String> {
+    let option = SolidityOption::new(Path::new("bin"), contract, SolidityOutputKind::AST);
+    match Solidity::with_providers(option, providers).compile() {
+        Err(SolidityError::CompilerNotFound(version)) => Ok(version),
+        Err(error) => Err(error.to_string()),
+        Ok(_) => Err("compiled without a compiler".to_string()),
+    }
+}
+
+#[test]
+fn default_version_without_pragma() {
+    let dir = setup_dir("default", &[("main.sol", "contract A {}")]);
+    assert_eq!(extract_version(&dir.join("main.sol"), vec![]), Ok("v0.4.24".to_string()));
+    let providers = vec![CachedProvider::new(&["0.5.17", "0.8.0"]), CachedProvider::released()];
+    assert_eq!(extract_version(&dir.join("main.sol"), providers), Ok("v0.4.24".to_string()));
+}
+
+#[test]
+fn newest_version_of_all_providers() {
+    let dir = setup_dir("newest", &[("main.sol", "pragma solidity ^0.4.24;\ncontract A {}")]);
+    let providers = vec![CachedProvider::new(&["0.4.11", "0.4.24", "0.5.0"]), CachedProvider::released()];
+    assert_eq!(extract_version(&dir.join("main.sol"), providers), Ok("v0.4.26".to_string()));
+    let providers = vec![CachedProvider::new(&["0.4.11", "0.4.24", "0.5.0"])];
+    assert_eq!(extract_version(&dir.join("main.sol"), providers), Ok("v0.4.24".to_string()));
+}
+
+#[test]
+fn offline_provider() {
+    let dir = setup_dir("offline", &[("main.sol", "pragma solidity ^0.4.24;\ncontract A {}")]);
+    let providers = vec![CachedProvider::new(&["0.4.25"]), Box::new(OfflineProvider) as Box<dyn CompilerProvider>];
+    assert_eq!(extract_version(&dir.join("main.sol"), providers), Ok("v0.4.25".to_string()));
+    let providers = vec![CachedProvider::new(&["0.5.0"]), Box::new(OfflineProvider) as Box<dyn CompilerProvider>];
+    let error = extract_version(&dir.join("main.sol"), providers).unwrap_err();
+    assert_eq!(error, "failed to download compiler: offline");
+}
+
+#[test]
+fn pragmas_of_imports_intersect() {
+    let dir = setup_dir("imports", &[
+        ("main.sol", "pragma solidity >=0.4.22 <0.6.0;\nimport \"./lib/math.sol\";\ncontract A {}"),
+        ("lib/math.sol", "pragma solidity ^0.5.0;\nimport \"../token.sol\";\nlibrary M {}"),
+        ("token.sol", "// pragma solidity ^0.4.0;\npragma solidity <=0.5.12;\ncontract T {}"),
+    ]);
+    let providers = vec![CachedProvider::new(&["0.4.24", "0.5.3"]), CachedProvider::released()];
+    assert_eq!(extract_version(&dir.join("main.sol"), providers), Ok("v0.5.12".to_string()));
+    let providers = vec![CachedProvider::new(&["0.4.24", "0.5.3"])];
+    assert_eq!(extract_version(&dir.join("main.sol"), providers), Ok("v0.5.3".to_string()));
+}
+
+#[test]
+fn unsatisfiable_pragmas() {
+    let dir = setup_dir("unsatisfiable", &[
+        ("main.sol", "pragma solidity ^0.4.24;\nimport \"./other.sol\";\ncontract A {}"),
+        ("other.sol", "pragma solidity ^0.5.0;\ncontract B {}"),
+    ]);
+    let error = extract_version(&dir.join("main.sol"), vec![CachedProvider::released()]).unwrap_err();
+    assert!(error.starts_with("no compiler satisfies"));
+}
+
+#[test]
+fn lowest_version() {
+    let lowest = |reqs: &[&str]| {
+        let reqs = reqs.iter().map(|req| VersionReq::parse(req).unwrap()).collect::<Vec<_>>();
+        VersionReq::lowest(&reqs).map(|version| version.to_string())
+    };
+    assert_eq!(lowest(&[]), Some("0.0.0".to_string()));
+    assert_eq!(lowest(&["^0.4.24"]), Some("0.4.24".to_string()));
+    assert_eq!(lowest(&[">0.4.24 <0.6.0"]), Some("0.4.25".to_string()));
+    assert_eq!(lowest(&["^0.5.0 || ^0.4.24", ">=0.4.26"]), Some("0.4.26".to_string()));
+    assert_eq!(lowest(&["<0.5.0", "<=0.4.11"]), Some("0.0.0".to_string()));
+    assert_eq!(lowest(&["^0.4.24", "^0.5.0"]), None);
+}
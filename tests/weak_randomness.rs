@@ -0,0 +1,23 @@
+mod setup;
+
+use std::io;
+use setup::setup_network;
+use ssa::oracle::{ Detector, WeakRandomnessDetector };
+
+#[test]
+fn weak_randomness() -> io::Result<()> {
+    setup_network("weak_randomness_1.sol", "Lottery", |network| {
+        let findings = WeakRandomnessDetector.check(&network);
+        let lines = findings.iter()
+            .map(|finding| finding.get_node().get_location().get_start().0)
+            .collect::<Vec<usize>>();
+        assert_eq!(lines, vec![14, 15, 20, 26, 27]);
+        let index = &findings[1];
+        assert!(index.get_message().starts_with("array index"));
+        assert_eq!(index.get_related_by_role("flow").len(), 2);
+        assert_eq!(index.get_related_by_role("source").len(), 2);
+        assert!(findings[2].get_message().contains("`blockhash`"));
+        assert!(findings[4].get_message().starts_with("ether amount"));
+    })?;
+    Ok(())
+}